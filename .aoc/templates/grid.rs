@@ -0,0 +1,46 @@
+//! {{title}}
+
+use advent_of_code::helpers::grid::Grid;
+
+/// The puzzle input as a character map.
+fn parse_grid(input: &str) -> Grid<char> {
+    Grid::from_chars(input)
+}
+
+pub fn part_one(input: &str) -> Option<{{part_one_type}}> {
+    let _grid = parse_grid(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{part_two_type}}> {
+    let _grid = parse_grid(input);
+    None
+}
+
+fn main() {
+    let input = &advent_of_code::read_year_file("inputs", {{year}}, {{day}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_year_file("examples", {{year}}, {{day}});
+        assert_eq!(part_one(&input), None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_year_file("examples", {{year}}, {{day}});
+        assert_eq!(part_two(&input), None);
+    }
+
+    #[test]
+    fn test_examples() {
+        advent_of_code::template::examples::check({{year}}, {{day}}, part_one, part_two);
+    }
+}
@@ -0,0 +1,58 @@
+//! {{title}}
+use advent_of_code::Error;
+use nom::{character::complete::u32, combinator::all_consuming, IResult};
+
+fn parse_line(line: &str) -> IResult<&str, u32> {
+    u32(line)
+}
+
+/// Parses every line of the input, reporting the first line that does not match.
+fn parse(input: &str) -> Result<Vec<u32>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            all_consuming(parse_line)(line)
+                .map(|(_, value)| value)
+                .map_err(|e| Error::new(e.to_string()).at_line(i + 1))
+        })
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<Option<{{part_one_type}}>, Error> {
+    let _values = parse(input)?;
+    Ok(None)
+}
+
+pub fn part_two(input: &str) -> Result<Option<{{part_two_type}}>, Error> {
+    let _values = parse(input)?;
+    Ok(None)
+}
+
+fn main() {
+    let input = &advent_of_code::read_year_file("inputs", {{year}}, {{day}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_year_file("examples", {{year}}, {{day}});
+        assert_eq!(part_one(&input), Ok(None));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_year_file("examples", {{year}}, {{day}});
+        assert_eq!(part_two(&input), Ok(None));
+    }
+
+    #[test]
+    fn test_examples() {
+        advent_of_code::template::examples::check({{year}}, {{day}}, part_one, part_two);
+    }
+}
@@ -1,49 +1,46 @@
+use advent_of_code::Error;
 use itertools::Itertools;
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let rations = input.split("\n\n");
+/// Sums up the calories of every elf's ration. Rations are separated by empty lines.
+fn parse_calorieses(input: &str) -> Result<Vec<i32>, Error> {
+    let mut calorieses = vec![];
+    let mut ration = None;
 
-    let max_calories = rations
-        .map(|ration| {
-            let calories = ration
-                .split('\n')
-                .map(|line| {
-                    line.parse::<i32>()
-                        .unwrap_or_else(|_| panic!("line {} is not a number", line))
-                })
-                .sum();
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            calorieses.extend(ration.take());
+            continue;
+        }
 
-            calories
-        })
-        .collect::<Vec<i32>>()
-        .into_iter()
-        .reduce(i32::max)
-        .unwrap_or_else(|| panic!("'Calorieses' is empty"));
+        let calories = line
+            .parse::<i32>()
+            .map_err(|_| Error::new(format!("\"{}\" is not a number", line)).at_line(i + 1))?;
+
+        *ration.get_or_insert(0) += calories;
+    }
 
-    Some(max_calories)
+    calorieses.extend(ration);
+    Ok(calorieses)
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
-    let rations = input.split("\n\n");
+pub fn part_one(input: &str) -> Result<i32, Error> {
+    let max_calories = parse_calorieses(input)?
+        .into_iter()
+        .reduce(i32::max)
+        .ok_or_else(|| Error::new("the input has no rations"))?;
 
-    let top_3_calorieses_sum: i32 = rations
-        .map(|ration| {
-            let calories: i32 = ration
-                .split('\n')
-                .map(|line| {
-                    line.parse::<i32>()
-                        .unwrap_or_else(|_| panic!("line {} is not a number", line))
-                })
-                .sum();
+    Ok(max_calories)
+}
 
-            calories
-        })
+pub fn part_two(input: &str) -> Result<i32, Error> {
+    let top_3_calorieses_sum: i32 = parse_calorieses(input)?
+        .into_iter()
         .sorted_unstable()
         .rev()
         .take(3)
         .sum();
 
-    Some(top_3_calorieses_sum)
+    Ok(top_3_calorieses_sum)
 }
 
 fn main() {
@@ -59,12 +56,21 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 1);
-        assert_eq!(part_one(&input), Some(24000));
+        assert_eq!(part_one(&input), Ok(24000));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 1);
-        assert_eq!(part_two(&input), Some(45000));
+        assert_eq!(part_two(&input), Ok(45000));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(
+            part_one("1000\n\n2x00"),
+            Err(Error::new("\"2x00\" is not a number").at_line(3))
+        );
+        assert_eq!(part_one("\n"), Err(Error::new("the input has no rations")));
     }
 }
@@ -11,7 +11,7 @@ pub fn part_one(input: &str) -> Option<i32> {
         let first = &line[0..half_len];
         let second = &line[half_len..len];
 
-        for (_, c) in first.chars().enumerate() {
+        for c in first.chars() {
             if second.contains(c) {
                 letters_common.push(c);
                 continue 'outer;
@@ -38,7 +38,7 @@ pub fn part_two(input: &str) -> Option<i32> {
         let second = lines.clone().nth(i + 1).unwrap();
         let third = lines.clone().nth(i + 2).unwrap();
 
-        for (_, c) in first.chars().enumerate() {
+        for c in first.chars() {
             if second.contains(c) && third.contains(c) {
                 badges.push(c);
                 continue 'outer;
@@ -6,8 +6,6 @@ pub fn part_one(input: &str) -> Option<String> {
     let line_str_len = cargo_string.lines().clone().next().unwrap().len();
     let line_len = (line_str_len + 1) / 4;
 
-    println!("Line length: {}", line_len);
-
     let mut cargos: Vec<Vec<char>> = vec![vec![]; line_len];
 
     'outer: for line in cargo_string.lines() {
@@ -64,8 +62,6 @@ pub fn part_two(input: &str) -> Option<String> {
     let line_str_len = cargo_string.lines().clone().next().unwrap().len();
     let line_len = (line_str_len + 1) / 4;
 
-    println!("Line length: {}", line_len);
-
     let mut cargos: Vec<Vec<char>> = vec![vec![]; line_len];
 
     'outer: for line in cargo_string.lines() {
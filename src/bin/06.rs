@@ -67,4 +67,9 @@ mod tests {
         let input = advent_of_code::read_file("examples", 6);
         assert_eq!(part_two(&input), Some(19));
     }
+
+    #[test]
+    fn test_examples() {
+        advent_of_code::template::examples::check(2022, 6, part_one, part_two);
+    }
 }
@@ -42,7 +42,21 @@ impl Entry {
     }
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
+/// Puzzle parameters of the device.
+pub struct Params {
+    /// Directories up to this size are counted in part one.
+    max_dir_size: i32,
+    total_space: i32,
+    required_space: i32,
+}
+
+pub const PARAMS: Params = Params {
+    max_dir_size: 100000,
+    total_space: 70000000,
+    required_space: 30000000,
+};
+
+pub fn part_one(input: &str, params: &Params) -> Option<i32> {
     let mut dir = Entry::new("/".to_string(), 0);
     let mut paths: Vec<String> = vec![];
 
@@ -89,13 +103,13 @@ pub fn part_one(input: &str) -> Option<i32> {
     let dirs = dir.get_dirs();
 
     let sizes = dirs.iter().map(|d| d.get_size()).collect::<Vec<i32>>();
-    let max = 100000;
+    let max = params.max_dir_size;
     let sizes_below_max: i32 = sizes.iter().filter(|s| **s < max).sum();
 
     Some(sizes_below_max)
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
+pub fn part_two(input: &str, params: &Params) -> Option<i32> {
     let mut dir = Entry::new("/".to_string(), 0);
     let mut paths: Vec<String> = vec![];
 
@@ -140,9 +154,9 @@ pub fn part_two(input: &str) -> Option<i32> {
     }
 
     let mut dirs = dir.get_dirs();
-    let device_size = 70000000;
+    let device_size = params.total_space;
     let device_size_remain = device_size - dir.get_size();
-    let should_be = 30000000;
+    let should_be = params.required_space;
 
     dirs.sort_unstable_by_key(|d| d.get_size());
 
@@ -159,8 +173,8 @@ pub fn part_two(input: &str) -> Option<i32> {
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 7);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, part_one, input, &PARAMS);
+    advent_of_code::solve!(2, part_two, input, &PARAMS);
 }
 
 #[cfg(test)]
@@ -170,12 +184,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_one(&input), Some(95437));
+        assert_eq!(part_one(&input, &PARAMS), Some(95437));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_two(&input), Some(24933642));
+        assert_eq!(part_two(&input, &PARAMS), Some(24933642));
     }
 }
@@ -1,174 +1,50 @@
+use advent_of_code::helpers::direction::Direction;
+use advent_of_code::helpers::vector::Vec2;
 use std::collections::HashSet;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn right(&mut self) {
-        self.x += 1;
-    }
-
-    fn left(&mut self) {
-        self.x -= 1;
-    }
-
-    fn up(&mut self) {
-        self.y += 1;
-    }
-
-    fn down(&mut self) {
-        self.y -= 1;
-    }
-
-    fn is_far(&self, other: &Point) -> bool {
-        (self.x - other.x).abs() > 1 || (self.y - other.y).abs() > 1
-    }
-
-    fn follow(&mut self, leader: &Point) {
-        if self.is_far(leader) {
-            if self.y + 2 <= leader.y {
-                match self.x.cmp(&leader.x) {
-                    std::cmp::Ordering::Greater => {
-                        self.up();
-                        self.left();
-                    }
-                    std::cmp::Ordering::Equal => {
-                        self.up();
-                    }
-                    std::cmp::Ordering::Less => {
-                        self.up();
-                        self.right();
-                    }
-                }
-            } else if self.y - 2 >= leader.y {
-                match self.x.cmp(&leader.x) {
-                    std::cmp::Ordering::Greater => {
-                        self.down();
-                        self.left();
-                    }
-                    std::cmp::Ordering::Equal => {
-                        self.down();
-                    }
-                    std::cmp::Ordering::Less => {
-                        self.down();
-                        self.right();
-                    }
-                }
-            } else if self.x + 2 <= leader.x {
-                match self.y.cmp(&leader.y) {
-                    std::cmp::Ordering::Greater => {
-                        self.right();
-                        self.down();
-                    }
-                    std::cmp::Ordering::Equal => {
-                        self.right();
-                    }
-                    std::cmp::Ordering::Less => {
-                        self.right();
-                        self.up();
-                    }
-                }
-            } else if self.x - 2 >= leader.x {
-                match self.y.cmp(&leader.y) {
-                    std::cmp::Ordering::Greater => {
-                        self.left();
-                        self.down();
-                    }
-                    std::cmp::Ordering::Equal => {
-                        self.left();
-                    }
-                    std::cmp::Ordering::Less => {
-                        self.left();
-                        self.up();
-                    }
-                }
-            } else {
-                panic!("{}, {} -> {}, {}", self.x, self.y, leader.x, leader.y);
-            }
-        }
+/// Moves `knot` one step towards `leader` unless they touch.
+fn follow(knot: &mut Vec2, leader: Vec2) {
+    if knot.chebyshev(leader) > 1 {
+        *knot += (leader - *knot).signum();
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let lines = input.lines();
-
-    let mut head = Point { x: 0, y: 0 };
-    let mut tail = Point { x: 0, y: 0 };
+/// Counts the positions visited by the tail of a rope of `len` knots.
+fn simulate(input: &str, len: usize) -> u32 {
+    let mut knots = vec![Vec2::ZERO; len];
 
     let mut tail_visited = HashSet::new();
-    tail_visited.insert(tail.clone());
+    tail_visited.insert(Vec2::ZERO);
 
-    for line in lines {
+    for line in input.lines() {
         let mut split = line.split_whitespace();
-        let direction = split.next().unwrap().chars().next().unwrap();
+        let direction = split
+            .next()
+            .and_then(|direction| direction.chars().next())
+            .and_then(Direction::parse)
+            .expect("Unknown direction");
         let distance = split.next().unwrap().parse::<u32>().unwrap();
 
         for _ in 0..distance {
-            match direction {
-                'R' => head.right(),
-                'L' => head.left(),
-                'U' => head.up(),
-                'D' => head.down(),
-                _ => panic!("Unknown direction"),
-            }
+            knots[0] += direction.delta();
 
-            tail.follow(&head);
-            tail_visited.insert(tail.clone());
+            for i in 1..len {
+                let leader = knots[i - 1];
+                follow(&mut knots[i], leader);
+            }
+            tail_visited.insert(knots[len - 1]);
         }
     }
 
-    Some(tail_visited.len() as u32)
+    tail_visited.len() as u32
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let lines = input.lines();
-
-    let mut points: Vec<Point> = vec![
-        Point { x: 0, y: 0 },
-        Point { x: 0, y: 0 },
-        Point { x: 0, y: 0 },
-        Point { x: 0, y: 0 },
-        Point { x: 0, y: 0 },
-        Point { x: 0, y: 0 },
-        Point { x: 0, y: 0 },
-        Point { x: 0, y: 0 },
-        Point { x: 0, y: 0 },
-        Point { x: 0, y: 0 },
-    ];
-
-    let mut tail_visited = HashSet::new();
-    tail_visited.insert(Point { x: 0, y: 0 });
-
-    for line in lines {
-        let mut split = line.split_whitespace();
-        let direction = split.next().unwrap().chars().next().unwrap();
-        let distance = split.next().unwrap().parse::<u32>().unwrap();
-
-        for _ in 0..distance {
-            let head = &mut points[0];
-
-            match direction {
-                'R' => head.right(),
-                'L' => head.left(),
-                'U' => head.up(),
-                'D' => head.down(),
-                _ => panic!("Unknown direction"),
-            }
-
-            for i in 0..9 {
-                let leader = points[i].clone();
-                let follower = &mut points[i + 1];
-
-                follower.follow(&leader);
-            }
-            tail_visited.insert(points[9].clone());
-        }
-    }
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(simulate(input, 2))
+}
 
-    Some(tail_visited.len() as u32)
+pub fn part_two(input: &str) -> Option<u32> {
+    Some(simulate(input, 10))
 }
 
 fn main() {
@@ -192,4 +68,9 @@ mod tests {
         let input = advent_of_code::read_file("examples", 9);
         assert_eq!(part_two(&input), Some(36));
     }
+
+    #[test]
+    fn test_examples() {
+        advent_of_code::template::examples::check(2022, 9, part_one, part_two);
+    }
 }
@@ -3,7 +3,7 @@ fn calc(cycles: u32, register: i32) -> i32 {
 }
 
 fn should_calc(cycles: u32) -> bool {
-    cycles >= 20 && (cycles - 20) % 40 == 0
+    cycles >= 20 && (cycles - 20).is_multiple_of(40)
 }
 
 struct Cpu {
@@ -38,7 +38,7 @@ impl Cpu {
         self.crt_position += 1;
 
         if self.cycles % 40 == 0 {
-            println!("{}", self.crt.iter().collect::<String>());
+            eprintln!("{}", self.crt.iter().collect::<String>());
             self.crt.clear();
             self.crt_position = 0;
         }
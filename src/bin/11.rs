@@ -156,7 +156,7 @@ pub fn part_one(input: &str) -> Option<u32> {
                 item.worry_level /= 3;
 
                 // test
-                let test_result = item.worry_level % monkey.test_divider.unwrap() as u64 == 0;
+                let test_result = item.worry_level % monkey.test_divider.unwrap() == 0;
 
                 // if true
                 if test_result {
@@ -310,7 +310,7 @@ pub fn part_two(input: &str) -> Option<u64> {
                 item.worry_level %= modulus;
 
                 // test
-                let test_result = item.worry_level % monkey.test_divider.unwrap() as u64 == 0;
+                let test_result = item.worry_level % monkey.test_divider.unwrap() == 0;
 
                 // if true
                 if test_result {
@@ -118,18 +118,18 @@ impl Packet {
 
 impl PartialOrd for Packet {
     fn partial_cmp(&self, other: &Packet) -> Option<Ordering> {
-        let order_result = self.data.compare(&other.data);
-        match order_result {
-            OrderResult::Right => Some(Ordering::Less),
-            OrderResult::NotRight => Some(Ordering::Greater),
-            OrderResult::Undetermined => Some(Ordering::Equal),
-        }
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Packet {
     fn cmp(&self, other: &Packet) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        let order_result = self.data.compare(&other.data);
+        match order_result {
+            OrderResult::Right => Ordering::Less,
+            OrderResult::NotRight => Ordering::Greater,
+            OrderResult::Undetermined => Ordering::Equal,
+        }
     }
 }
 
@@ -1,9 +1,8 @@
+use advent_of_code::helpers::grid::Grid;
 use core::panic;
 use std::{cmp::Ordering, fmt::Display};
 
-use ndarray::{s, Array2};
-
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 enum BlockState {
     #[default]
     Empty,
@@ -21,7 +20,7 @@ impl Display for BlockState {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 struct Block {
     state: BlockState,
 }
@@ -36,9 +35,9 @@ impl Block {
     }
 }
 
-struct Grid(Array2<Block>);
+struct Cave(Grid<Block>);
 
-impl Grid {
+impl Cave {
     fn new(paths: &Vec<Vec<(usize, usize)>>) -> Self {
         let biggest_x = *paths
             .iter()
@@ -55,17 +54,17 @@ impl Grid {
         let width = biggest_x + biggest_y;
         let height = biggest_y + 3;
 
-        let mut grid: Grid = Grid(Array2::<Block>::default((width, height)));
+        let mut grid = Cave(Grid::new(width, height, Block::default()));
 
         for x in 0..width {
-            grid.0[[x, height - 1]] = Block {
+            grid.0[(x, height - 1)] = Block {
                 state: BlockState::Rock,
             };
         }
 
         for path in paths {
             let (x, y) = path[0];
-            grid.0[[x, y]] = Block {
+            grid.0[(x, y)] = Block {
                 state: BlockState::Rock,
             };
             for i in 0..path.len() {
@@ -90,7 +89,7 @@ impl Grid {
                             Ordering::Equal => (),
                         }
 
-                        grid.0[[x, y]] = Block {
+                        grid.0[(x, y)] = Block {
                             state: BlockState::Rock,
                         };
                     }
@@ -102,23 +101,23 @@ impl Grid {
     }
 
     fn fall(&self, x: usize, y: usize) -> Option<(usize, usize)> {
-        let current = &self.0[[x, y]];
+        let current = &self.0[(x, y)];
         if current.is_blocked() {
             panic!("Block ({}, {}) is blocked", x, y);
         }
 
-        let belows = &self.0.slice(s![x, y + 1..]);
-        for (relative_y, below) in belows.indexed_iter() {
+        let belows = self.0.column(x).skip(y + 1);
+        for (relative_y, below) in belows.enumerate() {
             let below_y = y + relative_y + 1;
 
             if below.is_blocked() {
-                if let Some(left_down) = self.0.get((x - 1, below_y)) {
+                if let Some(left_down) = self.0.get(x - 1, below_y) {
                     if !left_down.is_blocked() {
                         return self.fall(x - 1, below_y);
                     }
                 }
 
-                if let Some(right_down) = self.0.get((x + 1, below_y)) {
+                if let Some(right_down) = self.0.get(x + 1, below_y) {
                     if !right_down.is_blocked() {
                         return self.fall(x + 1, below_y);
                     }
@@ -132,9 +131,9 @@ impl Grid {
     }
 
     fn is_out_of_bound(&self, x: usize, y: usize) -> bool {
-        match self.0.get((x, y)) {
+        match self.0.get(x, y) {
             Some(_) => {
-                let height = self.0.shape()[1]; // 12
+                let height = self.0.height(); // 12
                 y > height - 3
             }
             None => panic!("Out of bound ({}, {})", x, y),
@@ -162,7 +161,7 @@ fn parse_paths(input: &str) -> Vec<Vec<(usize, usize)>> {
 pub fn part_one(input: &str) -> Option<u32> {
     let paths = parse_paths(input);
 
-    let mut grid: Grid = Grid::new(&paths);
+    let mut grid = Cave::new(&paths);
 
     let start = (500, 0);
 
@@ -173,7 +172,7 @@ pub fn part_one(input: &str) -> Option<u32> {
             break;
         }
         count += 1;
-        grid.0[[x, y]] = Block {
+        grid.0[(x, y)] = Block {
             state: BlockState::Sand,
         };
     }
@@ -184,7 +183,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let paths = parse_paths(input);
 
-    let mut grid: Grid = Grid::new(&paths);
+    let mut grid = Cave::new(&paths);
 
     let start = (500, 0);
 
@@ -192,7 +191,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     while let Some((x, y)) = grid.fall(start.0, start.1) {
         count += 1;
-        grid.0[[x, y]] = Block {
+        grid.0[(x, y)] = Block {
             state: BlockState::Sand,
         };
         if (x, y) == (500, 0) {
@@ -1,140 +1,114 @@
+use advent_of_code::helpers::vector::Vec2;
 use nom::{bytes::complete::tag, IResult};
-
-trait Point {
-    fn x(&self) -> i32;
-    fn y(&self) -> i32;
-}
-
-#[derive(Debug, Clone)]
-struct Sensor {
-    x: i32,
-    y: i32,
-}
-
-impl Sensor {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Point for Sensor {
-    fn x(&self) -> i32 {
-        self.x
-    }
-
-    fn y(&self) -> i32 {
-        self.y
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Beacon {
-    x: i32,
-    y: i32,
-}
-
-impl Beacon {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Point for Beacon {
-    fn x(&self) -> i32 {
-        self.x
-    }
-
-    fn y(&self) -> i32 {
-        self.y
-    }
-}
+use std::collections::HashSet;
 
 #[derive(Debug)]
 struct Pair {
-    sensor: Sensor,
-    beacon: Beacon,
+    sensor: Vec2,
+    beacon: Vec2,
 }
 
 impl Pair {
-    fn new(sx: i32, sy: i32, bx: i32, by: i32) -> Self {
+    fn new(sx: i64, sy: i64, bx: i64, by: i64) -> Self {
         Self {
-            sensor: Sensor::new(sx, sy),
-            beacon: Beacon::new(bx, by),
+            sensor: Vec2::new(sx, sy),
+            beacon: Vec2::new(bx, by),
         }
     }
-}
 
-fn distance<T, U>(a: &T, b: &U) -> u32
-where
-    T: Point,
-    U: Point,
-{
-    let x = (a.x() - b.x()).unsigned_abs();
-    let y = (a.y() - b.y()).unsigned_abs();
-    x + y
+    /// Manhattan distance from the sensor to its closest beacon.
+    fn radius(&self) -> i64 {
+        self.sensor.manhattan(self.beacon)
+    }
 }
 
 /// Parse a line of input.
 /// Returns the sensor and beacon.
-/// `Sensor at x=[i32], y=[i32]: closest beacon is at x=[i32], y=[i32]`
+/// `Sensor at x=[i64], y=[i64]: closest beacon is at x=[i64], y=[i64]`
 fn parse_line(line: &str) -> IResult<&str, Pair> {
     let (input, _) = tag("Sensor at x=")(line)?;
-    let (input, x) = nom::character::complete::i32(input)?;
+    let (input, x) = nom::character::complete::i64(input)?;
     let (input, _) = tag(", y=")(input)?;
-    let (input, y) = nom::character::complete::i32(input)?;
+    let (input, y) = nom::character::complete::i64(input)?;
     let (input, _) = tag(": closest beacon is at x=")(input)?;
-    let (input, bx) = nom::character::complete::i32(input)?;
+    let (input, bx) = nom::character::complete::i64(input)?;
     let (input, _) = tag(", y=")(input)?;
-    let (input, by) = nom::character::complete::i32(input)?;
+    let (input, by) = nom::character::complete::i64(input)?;
 
     Ok((input, Pair::new(x, y, bx, by)))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let check_y = 2000000;
+/// Puzzle parameters that differ between the example and the real input.
+pub struct Params {
+    /// Row to count the positions without a beacon in.
+    check_y: i64,
+    /// Upper bound of both coordinates of the distress beacon.
+    max: i64,
+}
+
+pub const PARAMS: Params = Params {
+    check_y: 2_000_000,
+    max: 4_000_000,
+};
+
+#[cfg(test)]
+const EXAMPLE_PARAMS: Params = Params {
+    check_y: 10,
+    max: 20,
+};
+
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
+    let check_y = params.check_y;
 
     let pairs: Vec<Pair> = input
         .lines()
         .map(|line| parse_line(line).unwrap().1)
         .collect();
 
-    let mut checked = 0;
+    let mut ranges = vec![];
 
     for pair in &pairs {
-        let dist = distance(&pair.sensor, &pair.beacon) as i32;
+        let dist = pair.radius();
         if pair.sensor.y - dist <= check_y && check_y <= pair.sensor.y + dist {
             let dist = dist - (check_y - pair.sensor.y).abs();
-            let from = pair.sensor.x - dist;
-            let to = pair.sensor.x + dist;
-
-            for _ in from..=to {
-                checked += 1;
-            }
+            ranges.push((pair.sensor.x - dist, pair.sensor.x + dist));
         }
     }
 
-    pairs.iter().for_each(|pair| {
-        let beacon = &pair.beacon;
+    ranges.sort_unstable();
 
-        if beacon.y == check_y {
-            checked -= 1;
+    // merge overlapping ranges so no position is counted twice
+    let mut checked = 0;
+    let mut covered_to = i64::MIN;
+    for (from, to) in ranges {
+        let from = from.max(covered_to.saturating_add(1));
+        if from <= to {
+            checked += (to - from + 1) as u32;
+            covered_to = to;
         }
-    });
+    }
+
+    let beacons_on_row = pairs
+        .iter()
+        .map(|pair| &pair.beacon)
+        .filter(|beacon| beacon.y == check_y)
+        .map(|beacon| beacon.x)
+        .collect::<HashSet<_>>();
 
-    None
+    Some(checked - beacons_on_row.len() as u32)
 }
 
 // 340ms
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let max_x = 4_000_000;
+pub fn part_two(input: &str, params: &Params) -> Option<u64> {
+    let max_x = params.max;
 
-    let sensors_with_dists: Vec<(Sensor, u32)> = input
+    let sensors_with_dists: Vec<(Vec2, i64)> = input
         .lines()
         .map(|line| {
             let pair = parse_line(line).unwrap().1;
 
-            let dist = distance(&pair.sensor, &pair.beacon);
+            let dist = pair.radius();
 
             (pair.sensor, dist)
         })
@@ -144,10 +118,10 @@ pub fn part_two(input: &str) -> Option<u64> {
     let mut x = 0;
     let mut can_exit = false;
     'outer: loop {
-        let point = Beacon::new(x, y);
+        let point = Vec2::new(x, y);
 
         for (sensor, dist) in &sensors_with_dists {
-            let dist_to_s = distance(&point, sensor);
+            let dist_to_s = point.manhattan(*sensor);
 
             if dist_to_s > *dist {
                 can_exit = true;
@@ -156,7 +130,7 @@ pub fn part_two(input: &str) -> Option<u64> {
                 // not distress
 
                 // jump to next location
-                let x_to = *dist as i32 - (y - sensor.y).abs() + sensor.x + 1;
+                let x_to = *dist - (y - sensor.y).abs() + sensor.x + 1;
 
                 if x_to > max_x {
                     x = 0;
@@ -174,15 +148,15 @@ pub fn part_two(input: &str) -> Option<u64> {
         }
     }
 
-    let _frequency = (x as u64) * 4_000_000 + y as u64;
+    let frequency = (x as u64) * 4_000_000 + y as u64;
 
-    None
+    Some(frequency)
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 15);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, part_one, input, &PARAMS);
+    advent_of_code::solve!(2, part_two, input, &PARAMS);
 }
 
 #[cfg(test)]
@@ -192,12 +166,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 15);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input, &EXAMPLE_PARAMS), Some(26));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 15);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input, &EXAMPLE_PARAMS), Some(56000011));
     }
 }
@@ -1,8 +1,6 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-
+use advent_of_code::helpers::graph::{BitSet, Distances, Graph, Interner};
 use nom::IResult;
-
-type Routes<'a> = HashMap<&'a str, HashMap<&'a str, i32>>;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 struct Node<'a> {
@@ -22,7 +20,7 @@ impl<'a> Node<'a> {
 
     /// Parse a line of input into a Node.
     /// "Valve [id] has flow rate=[flow_rate]; tunnels lead to valves [connections]"
-    fn parse_line(input: &'a str) -> IResult<&str, Self> {
+    fn parse_line(input: &'a str) -> IResult<&'a str, Self> {
         let (input, _) = nom::bytes::complete::tag("Valve ")(input)?;
         let (input, id) = nom::bytes::complete::take_while(|c| c != ' ')(input)?;
         let (input, _) = nom::bytes::complete::tag(" has flow rate=")(input)?;
@@ -35,124 +33,124 @@ impl<'a> Node<'a> {
 
         Ok((input, Self::new(id, flow_rate, connections)))
     }
+}
 
-    /// Breadth-first search to find the shortest path to another node.
-    fn bfs(&self, nodes: &[Node], end: &Node) -> Option<i32> {
-        let mut queue = VecDeque::new();
-        let mut visited = HashSet::new();
-
-        queue.push_back((self.id, 0));
-
-        while let Some((id, depth)) = queue.pop_front() {
-            if id == end.id {
-                return Some(depth);
-            }
+/// The valves worth opening, with `AA` at index 0, and the minutes it takes to walk
+/// between any two of them.
+struct Network {
+    flow_rates: Vec<i32>,
+    distances: Distances,
+}
 
-            if visited.contains(&id) {
-                continue;
+impl Network {
+    fn parse(input: &str) -> Self {
+        let nodes = input
+            .lines()
+            .map(|line| Node::parse_line(line).unwrap().1)
+            .collect::<Vec<_>>();
+
+        let mut ids = Interner::new();
+        ids.intern("AA");
+
+        let mut tunnels = Graph::default();
+        for node in &nodes {
+            let from = ids.intern(node.id);
+            for connection in &node.connections {
+                tunnels.add_edge(from, ids.intern(connection), 1);
             }
+        }
 
-            visited.insert(id);
+        let valves: Vec<&Node> = nodes.iter().filter(|node| node.flow_rate != 0).collect();
 
-            for node in nodes {
-                if node.id == id {
-                    continue;
-                }
+        let keep: Vec<usize> = std::iter::once(0)
+            .chain(valves.iter().map(|node| ids.intern(node.id)))
+            .collect();
+        let flow_rates = std::iter::once(0)
+            .chain(valves.iter().map(|node| node.flow_rate))
+            .collect();
 
-                if node.connections.contains(&id) {
-                    queue.push_back((node.id, depth + 1));
-                }
-            }
+        Network {
+            flow_rates,
+            distances: tunnels.all_pairs_bfs().compress(&keep),
         }
-
-        None
     }
-}
-
-pub fn part_one(input: &str) -> Option<i32> {
-    let nodes = input
-        .lines()
-        .map(|line| Node::parse_line(line).unwrap().1)
-        .collect::<Vec<_>>();
 
-    let flow_rates: HashMap<&str, i32> =
-        nodes.iter().map(|node| (node.id, node.flow_rate)).collect();
-
-    let mut routes: Routes = HashMap::new();
-    for node in &nodes {
-        let mut route: HashMap<&str, i32> = HashMap::new();
+    /// The most pressure that can be released within `time` minutes starting at `AA`, for
+    /// every set of valves that can be opened in that time.
+    fn best_flows(&self, time: i32) -> HashMap<BitSet, i32> {
+        let mut best = HashMap::new();
+        self.visit(0, time, BitSet::EMPTY, 0, &mut best);
+        best
+    }
 
-        for other in &nodes {
-            if node.id == other.id {
+    /// Records `flow` for `opened`, then tries every valve that can still be reached and
+    /// opened in `time` minutes from valve `at`.
+    fn visit(
+        &self,
+        at: usize,
+        time: i32,
+        opened: BitSet,
+        flow: i32,
+        best: &mut HashMap<BitSet, i32>,
+    ) {
+        let entry = best.entry(opened).or_insert(0);
+        *entry = (*entry).max(flow);
+
+        for target in 1..self.flow_rates.len() {
+            if opened.contains(target) {
                 continue;
             }
 
-            if let Some(path) = node.bfs(&nodes, other) {
-                let other_flow_rate = flow_rates[&other.id];
-                if other_flow_rate != 0 {
-                    route.insert(other.id, path);
-                }
+            let distance = match self.distances.get(at, target) {
+                Some(distance) => distance as i32,
+                None => continue,
+            };
+
+            let time_left = time - distance - 1;
+            if time_left > 0 {
+                self.visit(
+                    target,
+                    time_left,
+                    opened.with(target),
+                    flow + self.flow_rates[target] * time_left,
+                    best,
+                );
             }
         }
-
-        if (!route.is_empty() && node.flow_rate != 0) || node.id == "AA" {
-            routes.insert(node.id, route.clone());
-        }
     }
+}
 
-    let key_valves = nodes
-        .iter()
-        .filter(|node| node.flow_rate != 0)
-        .map(|node| node.id)
-        .collect::<Vec<_>>();
+pub fn part_one(input: &str) -> Option<i32> {
+    let network = Network::parse(input);
 
-    let mut seen: HashSet<&str> = HashSet::new();
+    network.best_flows(30).into_values().max()
+}
 
-    let best_flow = find_best_total_flow("AA", 30, &mut seen, key_valves, &routes, &flow_rates);
+pub fn part_two(input: &str) -> Option<i32> {
+    let network = Network::parse(input);
 
-    Some(best_flow)
-}
+    // you and the elephant open disjoint sets of valves, so the best plan is the best pair
+    // of disjoint sets.
+    let mut flows: Vec<(BitSet, i32)> = network.best_flows(26).into_iter().collect();
+    flows.sort_unstable_by_key(|&(_, flow)| std::cmp::Reverse(flow));
 
-// https://topaz.github.io/paste/#XQAAAQDFEgAAAAAAAAA6GUrOH6M2DoM4hwZIidqO93SHStCJeyunMQe4sspt1/6hifYe49e9KxpBHPRdAAcbguOFwSv9afy94OXtRxapjrbqk9vZI/gik6tntQCBu3+RbrZ/eiIyTY9/wdsMixMbLioqKezyEzjto3/F5t1UnxcixJTI504eiu7XklYr1ZPYjtgShPGNlm5uC5zs48eLJGR7Agp92m1Atg+fJkBu7oef4wT0EZmQinviTzk0cQk9pIxp2bHbeIM0e+IyW45066p0yHgdiqv7ZP8WjeULxF8CQJciw/EWtPx0TBftSwqg/wQAkXxnN5f6UQpj61Zuq7j+Rnup85uiGa99xiRW9Igoyo/Eu5TrlIkcmfuM+8pVzdjOt9LSlVXFm/oU6Ol62z63NtuPd6mVX+x9vKq6LiwBKG/mETp3fG14/JICmIuPLcB/+idlKMrQM4WoXiN6kBuNSGqwa8aiP5FQZsGyXOy2eV4hN6Dt197PUrVTNw7VQm10PJgMj6hkrhR90LVMh2ZFBFp3brur/bnzO1eUsGaDMaCTWSGc08oUgQdpBOJu9v3mKVL9CeuB8h17es5KvsJqmdgzipjf0BJHrKypqGNCyDddK6XqL1D27IB0ofE6WB6JJBAkoICq6bgaGPNvUPtzZ04oFywTC/jps2CESnKpBJfgi5kfHhJyWldnLFGYP71s3pWFVxvRpV8Muvhmsr+FDs/cQNNY3NBEZNraEj83jHOJjPDgp4+1gmitXlz89hdu3PsWzKfJ1SeSC41rfPpGrEByihB625cwzacF0fq2c9pG4/126wAc2TYSsEH5H1URrYXPIwc/TQZvdGckWdlxdXqs3TFQuMHXHLTMQKO7IuOVyH1AZ8OXSYq3X7P7qtgicg1DQDZydoBsTC2K/K6qF644hHBYSWN4Zztmf4rxw6wPAYKbLJpA0BSibJ/OFUHZqmZvlk0+ZyWQVq/jeMa0vwqevKuwvJi3WD0eQdp/ZqNKopPBplXQxIO54/qhtSNMqsRL35hT3mDOyGPMNEiMV6dPYbI6mIsSMmYgaKpQwZeGDrSh9rrGXUrtgEiXQY3qzEyBo5OOPjGhqBVYQo63Pqyyh8S8neZaK5h5y9LXm/0EWqkHcurkiKqALGJ/JaPcS6HfJI67NkrU64WNqPzuWTB2ipauvLB4n7J1dSLgTSmAcK2p1D+E5MZYyz2bEJ3lXJocOMSCGuvXnu7KoR+1TYzHCWXAvD/9y3v0at5mEtIlSW7xsooOOzhdLdz+SPWAsplSLuoELQadgLcDZIjUGSukuzwZyowcetMwshYCRWbaRPmwCqVB1IZXXum12eH6fW8zqKuzUd42z13lI2ju2Cj3UJyhmuBGBlI61lWqb5+abL7fcfR6Id9o0wozUDLOEjU8ef46Tx10coyyKAPyy9/V1vN4kI42gHeKa0T01Etx5VKIpCwl421CUeDvWN2pHyXIC/F0G92eLgBE0HwGa9O+Z1XCtjSBtoOKBJ0p5sJL4dl4o9+CxUAKaUA9YOUZfM/LOZghFtPgIe8c+xwJOh7Ddc16D5MxfxouOLEM6kzh0KwOROr8HSyMBUc0zIUymmo7uEPNTOymHX4TlZnGbwkOntAYmUOS8UIswEHH4O1wXJa2bqo/5FnGeFmkXcverdUePmpibgzS8y2Xe679khnBD3oZivnrzOKVi194v6jpntuWrQi+6LD96att6+M73rkBSGapMLSlr519d9tQtEYvlxaCimMSaopH4+E3ToSYXhvT11JHSwhFZU70OGImQ/u5mkT+5yvZi8mV3QgzB7wwtyDdbVJjNH5ln+l6MjhVG2Y2n8rQLAJ32tzXuYteT1KYmMDY7Re48nJOYM8IrTRzgZpdAI4Wrqr1Lx5NcvM35d3TW0jIlFmA6j6Ii6d51tIbyQKhlKY7Myx1sO1bBJ+M3VjH4VJH78HdZr6AnvfZfEkvRhJWPJ9Ic1nWVKikMVU4DAR52iDfXzNeZ7FZlWXZvTIrmYj5BBB80ysh3jOMuCEPUdj4xRZ3kry2HL8cphs/A0bna/OfonxPsbgbhBqAoI02IoJyS4HGSMgrbKRWWH/FBjSvprMRlUzZTkUUXsDepeci2d2qlf6jwTMvUZvibf3/8F3H0w==
-fn find_best_total_flow(
-    current_id: &str,
-    time: i32,
-    seen: &mut HashSet<&str>,
-    targets: Vec<&str>,
-    routes: &Routes,
-    flow_rates: &HashMap<&str, i32>,
-) -> i32 {
-    let mut new_seen = seen.clone();
-    new_seen.insert(current_id);
-
-    // remove 'seen's from target
-    let targets = targets
-        .into_iter()
-        .filter(|target| !new_seen.contains(*target))
-        .collect::<Vec<_>>();
-
-    let mut best_flow = 0;
-    for target in &targets {
-        let time_left = time - routes[current_id][*target] - 1;
-        if time_left > 0 {
-            let mut flow = flow_rates[*target] * time_left;
-            flow += find_best_total_flow(
-                target,
-                time_left,
-                &mut new_seen,
-                targets.clone(),
-                routes,
-                flow_rates,
-            );
-            best_flow = best_flow.max(flow);
+    let mut best = 0;
+    for (i, &(mine, my_flow)) in flows.iter().enumerate() {
+        // the remaining pairs cannot beat `best` once twice this flow does not.
+        if my_flow * 2 <= best {
+            break;
+        }
+        for &(theirs, their_flow) in &flows[i..] {
+            if my_flow + their_flow <= best {
+                break;
+            }
+            if mine.is_disjoint(theirs) {
+                best = my_flow + their_flow;
+            }
         }
     }
 
-    best_flow
-}
-
-pub fn part_two(_input: &str) -> Option<u32> {
-    None
+    Some(best)
 }
 
 fn main() {
@@ -174,7 +172,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 16);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(1707));
     }
 
     #[test]
@@ -1,3 +1,6 @@
+use advent_of_code::helpers::grid::Grid;
+use std::collections::HashMap;
+
 #[derive(Debug, PartialEq, Eq)]
 enum Move {
     Left,
@@ -24,122 +27,134 @@ struct RockShape {
     rocks: Vec<Vec<bool>>,
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let moves = Move::parse(input).unwrap();
-    let base_rock_shapes = vec![
-        vec![vec![false, false, true, true, true, true, false]],
-        vec![
-            vec![false, false, false, true, false, false, false],
-            vec![false, false, true, true, true, false, false],
-            vec![false, false, false, true, false, false, false],
-        ],
-        vec![
-            vec![false, false, false, false, true, false, false],
-            vec![false, false, false, false, true, false, false],
-            vec![false, false, true, true, true, false, false],
-        ],
-        vec![
-            vec![false, false, true, false, false, false, false],
-            vec![false, false, true, false, false, false, false],
-            vec![false, false, true, false, false, false, false],
-            vec![false, false, true, false, false, false, false],
-        ],
-        vec![
-            vec![false, false, true, true, false, false, false],
-            vec![false, false, true, true, false, false, false],
-        ],
-    ];
-    let mut world: Vec<Vec<bool>> = vec![];
-    let mut loop_counter = 0;
-    let mut move_counter = 0;
-
-    loop {
-        let current_shape = base_rock_shapes[loop_counter % 5].clone();
+const ROCK_SHAPES: [&[[bool; 7]]; 5] = [
+    &[[false, false, true, true, true, true, false]],
+    &[
+        [false, false, false, true, false, false, false],
+        [false, false, true, true, true, false, false],
+        [false, false, false, true, false, false, false],
+    ],
+    &[
+        [false, false, false, false, true, false, false],
+        [false, false, false, false, true, false, false],
+        [false, false, true, true, true, false, false],
+    ],
+    &[
+        [false, false, true, false, false, false, false],
+        [false, false, true, false, false, false, false],
+        [false, false, true, false, false, false, false],
+        [false, false, true, false, false, false, false],
+    ],
+    &[
+        [false, false, true, true, false, false, false],
+        [false, false, true, true, false, false, false],
+    ],
+];
+
+/// The tower of settled rocks and where the rock and jet sequences are at. Row 0 of
+/// `world` is the bottom of the tower.
+struct Chamber {
+    moves: Vec<Move>,
+    world: Grid<bool>,
+    rock_counter: usize,
+    move_counter: usize,
+}
+
+impl Chamber {
+    fn new(input: &str) -> Self {
+        Self {
+            moves: Move::parse(input.trim()).unwrap(),
+            world: Grid::new(7, 0, false),
+            rock_counter: 0,
+            move_counter: 0,
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.world.height()
+    }
+
+    /// Lets the next rock fall until it settles.
+    fn drop_rock(&mut self) {
+        let current_shape = ROCK_SHAPES[self.rock_counter % ROCK_SHAPES.len()];
+        self.rock_counter += 1;
 
         let mut shape = RockShape {
-            height: world.len() as u32 + 3 + current_shape.len() as u32,
-            rocks: current_shape,
+            height: self.height() as u32 + 3 + current_shape.len() as u32,
+            rocks: current_shape.iter().map(|row| row.to_vec()).collect(),
         };
         for _ in 0..=shape.rocks.len() + 3 {
-            world.push(vec![false; 7]);
+            self.world.push_row([false; 7]);
         }
 
-        // println!("Rock {} begins falling", loop_counter + 1);
-        // dbg!(shape.height, world.len());
-
-        while can_fall(&shape, &world) {
-            let current_move = &moves[move_counter % moves.len()];
-            move_counter += 1;
+        while can_fall(&shape, &self.world) {
+            let current_move = &self.moves[self.move_counter % self.moves.len()];
+            self.move_counter += 1;
 
             shape.height -= 1;
-            // println!("Rock {} falls 1 unit to {}", loop_counter + 1, shape.height);
-
-            if can_move(&shape, &world, current_move) {
-                // println!("Rock {} moves {:?}", loop_counter + 1, current_move);
-                shape.rocks = shape
-                    .rocks
-                    .iter()
-                    .map(|row| match current_move {
-                        Move::Left => {
-                            let mut new_row = row.clone();
-                            new_row.remove(0);
-                            new_row.push(false);
-                            new_row
-                        }
-                        Move::Right => {
-                            let mut new_row = row.clone();
-                            new_row.remove(row.len() - 1);
-                            new_row.insert(0, false);
-                            new_row
-                        }
-                    })
-                    .collect();
-            } else {
-                // println!("Rock {} cannot move {:?}", loop_counter + 1, current_move);
+
+            if can_move(&shape, &self.world, current_move) {
+                shape.rocks.iter_mut().for_each(|row| match current_move {
+                    Move::Left => {
+                        row.remove(0);
+                        row.push(false);
+                    }
+                    Move::Right => {
+                        row.pop();
+                        row.insert(0, false);
+                    }
+                });
             }
         }
 
         for (dy, row) in shape.rocks.iter().enumerate() {
             for (x, &is_rock) in row.iter().enumerate() {
                 if is_rock {
-                    world[shape.height as usize - dy][x] = true;
+                    self.world[(x, shape.height as usize - dy)] = true;
                 }
             }
         }
 
-        world = world
-            .iter()
-            // remove all rows that are all false
-            .filter(|row| row.iter().any(|&b| b))
-            .cloned()
-            .collect();
-
-        // for row in world.iter().rev() {
-        //     for b in row {
-        //         print!("{}", if *b { '#' } else { '.' });
-        //     }
-        //     println!();
-        // }
-
-        // println!();
-
-        if loop_counter == 2021 {
-            // actually 2022, but we're counting from 0
-            break;
+        // remove all rows that are all false
+        self.world.retain_rows(|row| row.iter().any(|&b| b));
+    }
+
+    /// What decides how the next rocks fall: the next shape, the next jet and, per
+    /// column, how far below the top its highest rock is.
+    fn state(&self) -> (usize, usize, [usize; 7]) {
+        let mut surface = [self.height(); 7];
+        for (x, depth) in surface.iter_mut().enumerate() {
+            if let Some(d) = self.world.column(x).rev().position(|&is_rock| is_rock) {
+                *depth = d;
+            }
         }
-        loop_counter += 1;
+
+        (
+            self.rock_counter % ROCK_SHAPES.len(),
+            self.move_counter % self.moves.len(),
+            surface,
+        )
     }
+}
 
-    Some(world.len() as u32)
+pub fn part_one(input: &str) -> Option<u32> {
+    let mut chamber = Chamber::new(input);
+
+    for _ in 0..2022 {
+        chamber.drop_rock();
+    }
+
+    Some(chamber.height() as u32)
 }
 
-fn can_fall(shape: &RockShape, world: &[Vec<bool>]) -> bool {
-    if shape.height == 0 {
+fn can_fall(shape: &RockShape, world: &Grid<bool>) -> bool {
+    // the bottom row of the shape rests on the floor
+    if (shape.height as usize) < shape.rocks.len() {
         return false;
     }
     for (dy, row) in shape.rocks.iter().enumerate() {
         for (x, &is_rock) in row.iter().enumerate() {
-            if is_rock && world[shape.height as usize - dy - 1][x] {
+            if is_rock && world[(x, shape.height as usize - dy - 1)] {
                 return false;
             }
         }
@@ -147,7 +162,7 @@ fn can_fall(shape: &RockShape, world: &[Vec<bool>]) -> bool {
     true
 }
 
-fn can_move(shape: &RockShape, world: &[Vec<bool>], direction: &Move) -> bool {
+fn can_move(shape: &RockShape, world: &Grid<bool>, direction: &Move) -> bool {
     match direction {
         Move::Left => {
             if shape.rocks.iter().any(|row| row[0]) {
@@ -155,7 +170,7 @@ fn can_move(shape: &RockShape, world: &[Vec<bool>], direction: &Move) -> bool {
             } else {
                 for (dy, row) in shape.rocks.iter().enumerate() {
                     for (x, &is_rock) in row.iter().enumerate() {
-                        if is_rock && world[shape.height as usize - dy][x - 1] {
+                        if is_rock && world[(x - 1, shape.height as usize - dy)] {
                             return false;
                         }
                     }
@@ -169,7 +184,7 @@ fn can_move(shape: &RockShape, world: &[Vec<bool>], direction: &Move) -> bool {
             } else {
                 for (dy, row) in shape.rocks.iter().enumerate() {
                     for (x, &is_rock) in row.iter().enumerate() {
-                        if is_rock && world[shape.height as usize - dy][x + 1] {
+                        if is_rock && world[(x + 1, shape.height as usize - dy)] {
                             return false;
                         }
                     }
@@ -180,88 +195,34 @@ fn can_move(shape: &RockShape, world: &[Vec<bool>], direction: &Move) -> bool {
     }
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
-    None
-    // let moves = Move::parse(input).unwrap();
-    // let moves_len = moves.len();
-    // let base_rock_shapes = vec![
-    //     vec![vec![false, false, true, true, true, true, false]],
-    //     vec![
-    //         vec![false, false, false, true, false, false, false],
-    //         vec![false, false, true, true, true, false, false],
-    //         vec![false, false, false, true, false, false, false],
-    //     ],
-    //     vec![
-    //         vec![false, false, false, false, true, false, false],
-    //         vec![false, false, false, false, true, false, false],
-    //         vec![false, false, true, true, true, false, false],
-    //     ],
-    //     vec![
-    //         vec![false, false, true, false, false, false, false],
-    //         vec![false, false, true, false, false, false, false],
-    //         vec![false, false, true, false, false, false, false],
-    //         vec![false, false, true, false, false, false, false],
-    //     ],
-    //     vec![
-    //         vec![false, false, true, true, false, false, false],
-    //         vec![false, false, true, true, false, false, false],
-    //     ],
-    // ];
-    // let mut world: Vec<Vec<bool>> = vec![];
-    // let mut loop_counter = 0;
-    // let mut move_counter = 0;
-
-    // loop {
-    //     let current_shape = base_rock_shapes[loop_counter % 5].clone();
-
-    //     let mut shape = RockShape {
-    //         height: world.len() as u32 + 3 + current_shape.len() as u32,
-    //         rocks: current_shape,
-    //     };
-    //     for _ in 0..=shape.rocks.len() + 3 {
-    //         world.push(vec![false; 7]);
-    //     }
-
-    //     while can_fall(&shape, &world) {
-    //         let current_move = &moves[move_counter % moves_len];
-    //         move_counter += 1;
-
-    //         shape.height -= 1;
-
-    //         if can_move(&shape, &world, current_move) {
-    //             shape.rocks.iter_mut().for_each(|row| match current_move {
-    //                 Move::Left => {
-    //                     row.remove(0);
-    //                     row.push(false);
-    //                 }
-    //                 Move::Right => {
-    //                     row.remove(row.len() - 1);
-    //                     row.insert(0, false);
-    //                 }
-    //             });
-    //         }
-    //     }
-
-    //     if move_counter % moves_len == 0 {
-    //         println!("Move: {}, Rocks: {}", move_counter, loop_counter + 1);
-    //     }
-    //     for (dy, row) in shape.rocks.iter().enumerate() {
-    //         for (x, &is_rock) in row.iter().enumerate() {
-    //             if is_rock {
-    //                 world[shape.height as usize - dy][x] = true;
-    //             }
-    //         }
-    //     }
-
-    //     world.retain(|row| row.iter().any(|&b| b));
-
-    //     if loop_counter == 999_999_999_999 {
-    //         break;
-    //     }
-    //     loop_counter += 1;
-    // }
-
-    // Some(world.len() as u32)
+pub fn part_two(input: &str) -> Option<u64> {
+    const ROCKS: usize = 1_000_000_000_000;
+
+    let mut chamber = Chamber::new(input);
+    // rocks dropped and tower height, by the state they led to.
+    let mut seen: HashMap<(usize, usize, [usize; 7]), (usize, usize)> = HashMap::new();
+
+    while chamber.rock_counter < ROCKS {
+        chamber.drop_rock();
+
+        let (rocks, height) = (chamber.rock_counter, chamber.height());
+        if let Some((previous_rocks, previous_height)) =
+            seen.insert(chamber.state(), (rocks, height))
+        {
+            // the same state falls out the same way, so everything from here repeats.
+            let cycle = rocks - previous_rocks;
+            let cycles = (ROCKS - rocks) / cycle;
+
+            for _ in 0..(ROCKS - rocks) % cycle {
+                chamber.drop_rock();
+            }
+
+            let skipped = cycles * (height - previous_height);
+            return Some((chamber.height() + skipped) as u64);
+        }
+    }
+
+    Some(chamber.height() as u64)
 }
 
 fn main() {
@@ -283,6 +244,6 @@ mod tests {
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 17);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(1514285714288));
     }
 }
@@ -1,143 +1,68 @@
+use advent_of_code::helpers::search::bfs;
+use advent_of_code::helpers::vector::Vec3;
 use std::collections::HashSet;
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone)]
-struct Cube {
-    x: i32,
-    y: i32,
-    z: i32,
+fn parse(input: &str) -> HashSet<Vec3> {
+    input
+        .lines()
+        .map(|line| Vec3::parse(line).unwrap())
+        .collect()
 }
 
-impl Cube {
-    fn parse(input: &str) -> Self {
-        let mut split = input.split(',');
-        let x = split.next().unwrap().parse().unwrap();
-        let y = split.next().unwrap().parse().unwrap();
-        let z = split.next().unwrap().parse().unwrap();
-
-        Self { x, y, z }
-    }
-
-    /// Find 6 neighbors of a cube
-    fn neighbors(&self) -> Vec<Self> {
-        let neighbors = vec![
-            // Up
-            Self {
-                x: self.x,
-                y: self.y,
-                z: self.z + 1,
-            },
-            // Down
-            Self {
-                x: self.x,
-                y: self.y,
-                z: self.z - 1,
-            },
-            // Left
-            Self {
-                x: self.x - 1,
-                y: self.y,
-                z: self.z,
-            },
-            // Right
-            Self {
-                x: self.x + 1,
-                y: self.y,
-                z: self.z,
-            },
-            // Front
-            Self {
-                x: self.x,
-                y: self.y + 1,
-                z: self.z,
-            },
-            // Back
-            Self {
-                x: self.x,
-                y: self.y - 1,
-                z: self.z,
-            },
-        ];
-
-        neighbors
-    }
-
-    /// Finds all cubes while moving from `self` to `target` using dfs
-    /// If `cubes` contains `self` or `target`, they will be skipped
-    fn find_cubes(&self, target: &Self, cubes: &HashSet<Self>, min: i32, max: i32) -> Vec<Self> {
-        let mut cubes = cubes.clone();
-        cubes.remove(self);
-        cubes.remove(target);
-
-        let mut stack = vec![self.clone()];
-        let mut visited = HashSet::new();
-        let mut outsides = vec![];
-
-        while let Some(cube) = stack.pop() {
-            if visited.contains(&cube) {
-                continue;
-            }
-
-            visited.insert(cube.clone());
-
-            let is_outside = cube.x < min
-                || cube.x > max
-                || cube.y < min
-                || cube.y > max
-                || cube.z < min
-                || cube.z > max;
+/// Finds all air reachable from `start` without leaving the box `min..=max` on any axis.
+fn flood(start: Vec3, cubes: &HashSet<Vec3>, min: i64, max: i64) -> HashSet<Vec3> {
+    let inside = |cube: &Vec3| {
+        [cube.x, cube.y, cube.z]
+            .iter()
+            .all(|&c| c >= min && c <= max)
+    };
+
+    bfs(
+        [start],
+        |cube| {
+            cube.neighbors6()
+                .filter(|neighbor| inside(neighbor) && !cubes.contains(neighbor))
+                .collect::<Vec<_>>()
+        },
+        |_| false,
+    )
+    .distances
+    .into_keys()
+    .collect()
+}
 
-            if !is_outside {
-                outsides.push(cube.clone());
-            }
+/// Puzzle parameters that differ between the example and the real input.
+pub struct Params {
+    /// Every cube lies within `0..size` on all axes.
+    size: i64,
+}
 
-            for neighbor in cube.neighbors() {
-                if !visited.contains(&neighbor) && !cubes.contains(&neighbor) && !is_outside {
-                    stack.push(neighbor);
-                }
-            }
-        }
+pub const PARAMS: Params = Params { size: 25 };
 
-        outsides
-    }
-}
+#[cfg(test)]
+const EXAMPLE_PARAMS: Params = Params { size: 8 };
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let cubes: HashSet<Cube> = input.lines().map(Cube::parse).collect();
+    let cubes = parse(input);
 
     let cubes_sides = count_sides(&cubes);
 
     Some(cubes_sides)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let grid: HashSet<Cube> = (0..25)
-        .flat_map(|x| (0..25).flat_map(move |y| (0..25).map(move |z| Cube { x, y, z })))
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
+    let size = params.size;
+    let grid: HashSet<Vec3> = (0..size)
+        .flat_map(|x| (0..size).flat_map(move |y| (0..size).map(move |z| Vec3::new(x, y, z))))
         .collect();
 
-    let cubes: HashSet<Cube> = input.lines().map(Cube::parse).collect();
+    let cubes = parse(input);
 
-    let outsides = Cube {
-        x: -1,
-        y: -1,
-        z: -1,
-    }
-    .find_cubes(
-        &Cube {
-            x: 24,
-            y: 24,
-            z: 24,
-        },
-        &cubes,
-        -1,
-        24,
-    );
-
-    let cubes_plus_outsides: HashSet<Cube> = cubes
-        .union(&outsides.iter().cloned().collect())
-        .cloned()
-        .collect();
+    let outsides = flood(Vec3::new(-1, -1, -1), &cubes, -1, size - 1);
+
+    let cubes_plus_outsides: HashSet<Vec3> = cubes.union(&outsides).cloned().collect();
 
-    let insides: HashSet<Cube> = grid.difference(&cubes_plus_outsides).cloned().collect();
+    let insides: HashSet<Vec3> = grid.difference(&cubes_plus_outsides).cloned().collect();
 
     let cubes_sides = count_sides(&cubes);
     let insides_sides = count_sides(&insides);
@@ -145,12 +70,12 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(cubes_sides - insides_sides)
 }
 
-fn count_sides(cubes: &HashSet<Cube>) -> u32 {
+fn count_sides(cubes: &HashSet<Vec3>) -> u32 {
     let len = cubes.len();
     let mut duplicates_count = 0;
 
     for cube in cubes {
-        for new_cube in cube.neighbors() {
+        for new_cube in cube.neighbors6() {
             if cubes.contains(&new_cube) {
                 duplicates_count += 1;
             }
@@ -163,7 +88,7 @@ fn count_sides(cubes: &HashSet<Cube>) -> u32 {
 fn main() {
     let input = &advent_of_code::read_file("inputs", 18);
     advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(2, part_two, input, &PARAMS);
 }
 
 #[cfg(test)]
@@ -179,6 +104,6 @@ mod tests {
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 18);
-        assert_eq!(part_two(&input), Some(58));
+        assert_eq!(part_two(&input, &EXAMPLE_PARAMS), Some(58));
     }
 }
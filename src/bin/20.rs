@@ -12,7 +12,6 @@ impl Num {
 
 trait Nums {
     fn shift(&mut self, index: usize);
-    fn to_string(&self) -> String;
 }
 
 impl Nums for Vec<Num> {
@@ -27,13 +26,6 @@ impl Nums for Vec<Num> {
             self.insert(new_index as usize, num);
         }
     }
-
-    fn to_string(&self) -> String {
-        self.iter()
-            .map(|num| num.value.to_string())
-            .collect::<Vec<_>>()
-            .join(" ")
-    }
 }
 
 pub fn part_one(input: &str) -> Option<i64> {
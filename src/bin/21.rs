@@ -115,8 +115,8 @@ pub fn part_two(input: &str) -> Option<u64> {
     let other = get_number(&monkeys, &other_name);
     let other_2 = get_number(&monkeys, &other_2_name);
 
-    println!("{} = {} when humn = {}", other, other_2, PART_TWO_ANSWER);
-    println!("Solved equation by hand");
+    eprintln!("{} = {} when humn = {}", other, other_2, PART_TWO_ANSWER);
+    eprintln!("Solved equation by hand");
 
     None
 }
@@ -1,13 +1,12 @@
+use advent_of_code::helpers::direction::Direction;
+use advent_of_code::helpers::grid::Grid;
+use advent_of_code::helpers::vector::Vec2;
+
 #[derive(Debug)]
 enum Command {
     Step(usize),
-    Turn(DirectionCommand),
-}
-
-#[derive(Debug)]
-enum DirectionCommand {
-    Left,
-    Right,
+    TurnLeft,
+    TurnRight,
 }
 
 fn parse_commands(input: &str) -> Vec<Command> {
@@ -26,12 +25,12 @@ fn parse_commands(input: &str) -> Vec<Command> {
             }
             'L' => {
                 commands.push(Command::Step(current_number));
-                commands.push(Command::Turn(DirectionCommand::Left));
+                commands.push(Command::TurnLeft);
                 current_number = 0;
             }
             'R' => {
                 commands.push(Command::Step(current_number));
-                commands.push(Command::Turn(DirectionCommand::Right));
+                commands.push(Command::TurnRight);
                 current_number = 0;
             }
             _ => panic!("unexpected character"),
@@ -41,29 +40,19 @@ fn parse_commands(input: &str) -> Vec<Command> {
     commands
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Right = 0,
-    Down = 1,
-    Left = 2,
-    Up = 3,
-}
-
-#[derive(Debug, Clone)]
-struct Point {
-    x: usize,
-    y: usize,
-}
-
-impl Point {
-    fn new(x: usize, y: usize) -> Point {
-        Point { x, y }
+/// The facing part of the password: right is 0, then clockwise.
+fn facing(direction: Direction) -> usize {
+    match direction {
+        Direction::Right => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Up => 3,
     }
 }
 
 #[derive(Debug)]
 struct Map {
-    cells: Vec<Vec<Cell>>,
+    cells: Grid<Cell>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -74,59 +63,36 @@ enum Cell {
 }
 
 impl Map {
-    fn width(&self) -> usize {
-        // longest row width
-        self.cells.iter().map(|row| row.len()).max().unwrap()
+    /// Parses the map, padding rows shorter than the longest one with void.
+    fn parse(input: &str) -> Map {
+        let width = input.lines().map(|line| line.len()).max().unwrap_or(0);
+        let padded = input
+            .lines()
+            .map(|line| format!("{:<width$}", line, width = width))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let cells = Grid::parse(&padded, |c| match c {
+            '#' => Some(Cell::Wall),
+            '.' => Some(Cell::Open),
+            ' ' => Some(Cell::Void),
+            _ => None,
+        })
+        .unwrap();
+
+        Map { cells }
     }
 
-    fn height(&self) -> usize {
-        self.cells.len()
+    /// The next point in `direction`, wrapping around the edges of the map.
+    fn get_next_point(&self, point: Vec2, direction: Direction) -> Vec2 {
+        let next = point + direction.delta();
+        let (x, y) = self.cells.wrap(next.x as isize, next.y as isize);
+        Vec2::new(x as i64, y as i64)
     }
 
-    fn get_next_point(&self, point: &Point, direction: &Direction) -> Point {
-        let x = point.x;
-        let y = point.y;
-
-        let width = self.width();
-        let height = self.height();
-
-        match direction {
-            Direction::Up => {
-                if y == 0 {
-                    Point::new(x, height - 1)
-                } else {
-                    Point::new(x, y - 1)
-                }
-            }
-            Direction::Down => {
-                if y == height - 1 {
-                    Point::new(x, 0)
-                } else {
-                    Point::new(x, y + 1)
-                }
-            }
-            Direction::Left => {
-                if x == 0 {
-                    Point::new(width - 1, y)
-                } else {
-                    Point::new(x - 1, y)
-                }
-            }
-            Direction::Right => {
-                if x == width - 1 {
-                    Point::new(0, y)
-                } else {
-                    Point::new(x + 1, y)
-                }
-            }
-        }
-    }
-
-    fn get_cell(&self, point: &Point) -> Cell {
-        // self.cells[point.y][point.x].clone()
+    fn get_cell(&self, point: Vec2) -> Cell {
         self.cells
-            .get(point.y)
-            .and_then(|row| row.get(point.x))
+            .get_signed(point.x as isize, point.y as isize)
             .cloned()
             .unwrap_or(Cell::Void)
     }
@@ -134,30 +100,14 @@ impl Map {
 
 pub fn part_one(input: &str) -> Option<usize> {
     let mut split = input.split("\n\n");
-    let map = Map {
-        cells: split
-            .next()
-            .unwrap()
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| match c {
-                        '#' => Cell::Wall,
-                        '.' => Cell::Open,
-                        ' ' => Cell::Void,
-                        _ => panic!("unexpected character"),
-                    })
-                    .collect()
-            })
-            .collect(),
-    };
+    let map = Map::parse(split.next().unwrap());
 
     let desc = split.next().unwrap();
 
     let commands = parse_commands(desc);
 
-    let start: Point = Point::new(
-        map.cells[0].iter().position(|c| *c == Cell::Open).unwrap(),
+    let start = Vec2::new(
+        map.cells.row(0).position(|c| *c == Cell::Open).unwrap() as i64,
         0,
     );
 
@@ -169,20 +119,19 @@ pub fn part_one(input: &str) -> Option<usize> {
             Command::Step(step) => {
                 let mut i: i32 = 0;
 
-                println!("{:?} {:?}, {}", current_direction, current_point, step);
                 while i < *step as i32 {
-                    let next_point = map.get_next_point(&current_point, &current_direction);
-                    let cell = map.get_cell(&next_point);
+                    let next_point = map.get_next_point(current_point, current_direction);
+                    let cell = map.get_cell(next_point);
 
                     match cell {
                         Cell::Open => current_point = next_point,
                         Cell::Wall => break,
                         Cell::Void => {
-                            let mut check = map.get_next_point(&current_point, &current_direction);
-                            while map.get_cell(&check) == Cell::Void {
-                                check = map.get_next_point(&check, &current_direction);
+                            let mut check = map.get_next_point(current_point, current_direction);
+                            while map.get_cell(check) == Cell::Void {
+                                check = map.get_next_point(check, current_direction);
                             }
-                            match map.get_cell(&check) {
+                            match map.get_cell(check) {
                                 Cell::Open => {
                                     current_point = check;
                                 }
@@ -195,35 +144,14 @@ pub fn part_one(input: &str) -> Option<usize> {
                     i += 1;
                 }
             }
-            Command::Turn(direction) => {
-                current_direction = match &direction {
-                    DirectionCommand::Left => match &current_direction {
-                        Direction::Up => Direction::Left,
-                        Direction::Down => Direction::Right,
-                        Direction::Left => Direction::Down,
-                        Direction::Right => Direction::Up,
-                    },
-                    DirectionCommand::Right => match &current_direction {
-                        Direction::Up => Direction::Right,
-                        Direction::Down => Direction::Left,
-                        Direction::Left => Direction::Up,
-                        Direction::Right => Direction::Down,
-                    },
-                }
-            }
+            Command::TurnLeft => current_direction = current_direction.turn_left(),
+            Command::TurnRight => current_direction = current_direction.turn_right(),
         }
     }
 
-    let row = current_point.y + 1;
-    let column = current_point.x + 1;
-    let answer = (row * 1000) + (column * 4) + current_direction as usize;
-    let point = Point::new(column, row);
-    println!("{:?}", map.get_cell(&point));
-
-    println!(
-        "row: {}, column: {}, direction: {:?}",
-        row, column, current_direction
-    );
+    let row = current_point.y as usize + 1;
+    let column = current_point.x as usize + 1;
+    let answer = (row * 1000) + (column * 4) + facing(current_direction);
 
     Some(answer)
 }
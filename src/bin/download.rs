@@ -2,104 +2,153 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use std::io::Write;
-use std::path::PathBuf;
-use std::{env::temp_dir, io, process::Command};
-use std::{fs, process};
+use advent_of_code::template::answers::Answers;
+use advent_of_code::template::cache;
+use advent_of_code::template::client::Client;
+use advent_of_code::template::config::{default_year, parse_year};
+use advent_of_code::template::input::write_atomic;
+use advent_of_code::template::layout::data_path;
+use advent_of_code::template::puzzle::Puzzle;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fs, io, process};
 
 struct Args {
     day: u8,
-    year: Option<i16>,
+    year: Option<u16>,
+    /// Only download the input, not the puzzle description.
+    input_only: bool,
+    /// Replace an existing input with a fresh download.
+    refresh: bool,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
         day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
+        year: args.opt_value_from_fn(["-y", "--year"], parse_year)?,
+        input_only: args.contains("--input-only"),
+        refresh: args.contains("--refresh"),
     })
 }
 
-fn remove_file(path: &PathBuf) {
-    #[allow(unused_must_use)]
-    {
-        fs::remove_file(path);
-    }
+/// Writes the input to `src/inputs`, taking it from the cache unless `refresh` is set.
+/// Fresh downloads are stored in the cache with their checksum first.
+fn save_input(client: &Client, year: u16, day: u8, refresh: bool) -> io::Result<()> {
+    let input_path = data_path("inputs", year, day);
+    let user = client.user();
+
+    let cached = match refresh {
+        true => None,
+        false => cache::cached(&user, year, day)?,
+    };
+
+    let input = match cached {
+        Some(input) => {
+            println!("Restoring input of {} day {} from the cache", year, day);
+            input
+        }
+        None => {
+            println!("Downloading input of {} day {}", year, day);
+            let input = client.input(year, day)?;
+            let fetched = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_secs());
+            cache::store(&user, year, day, &input, fetched)?;
+            input
+        }
+    };
+
+    write_atomic(&input_path, &input)?;
+    println!("Wrote input to \"{}\"", input_path.display());
+    Ok(())
 }
 
-fn exit_with_status(status: i32, path: &PathBuf) -> ! {
-    remove_file(path);
-    process::exit(status);
+/// Writes the description to `src/puzzles/NN.md`. The example and its expected answers are
+/// only filled in where `src/examples` has nothing yet, so hand-made fixes are kept.
+fn save_puzzle(year: u16, day: u8, puzzle: &Puzzle) -> io::Result<()> {
+    let markdown_path = data_path("puzzles", year, day).with_extension("md");
+    write_atomic(&markdown_path, &puzzle.markdown)?;
+    println!("Wrote description to \"{}\"", markdown_path.display());
+
+    let example_path = data_path("examples", year, day);
+    let has_example = fs::read_to_string(&example_path).is_ok_and(|example| !example.is_empty());
+
+    match (&puzzle.example, has_example) {
+        (Some(example), false) => {
+            write_atomic(&example_path, example)?;
+            println!("Wrote example to \"{}\"", example_path.display());
+        }
+        (None, false) => eprintln!("Found no example in the description"),
+        (_, true) => {}
+    }
+
+    let expected_path = example_path.with_extension("expected");
+    let mut expected = match fs::read_to_string(&expected_path) {
+        Ok(contents) => Answers::parse(&contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Answers::default(),
+        Err(e) => return Err(e),
+    };
+
+    if expected.fill(&puzzle.expected) {
+        write_atomic(&expected_path, &expected.to_string())?;
+        println!("Wrote expected answers to \"{}\"", expected_path.display());
+    }
+
+    Ok(())
 }
 
 fn main() {
-    // acquire a temp file path to write aoc-cli output to.
-    // aoc-cli expects this file not to be present - delete just in case.
-    let mut tmp_file_path = temp_dir();
-    tmp_file_path.push("aoc_input_tmp");
-    remove_file(&tmp_file_path);
-
     let args = match parse_args() {
         Ok(args) => args,
         Err(e) => {
             eprintln!("Failed to process arguments: {}", e);
-            exit_with_status(1, &tmp_file_path);
+            process::exit(1);
         }
     };
 
-    let day_padded = format!("{:02}", args.day);
-    let input_path = format!("src/inputs/{}.txt", day_padded);
+    let year = match args.year.map_or_else(default_year, Ok) {
+        Ok(year) => year,
+        Err(e) => {
+            eprintln!("Failed to read config: {}", e);
+            process::exit(1);
+        }
+    };
 
-    // check if aoc binary exists and is callable.
-    if Command::new("aoc").arg("-V").output().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        exit_with_status(1, &tmp_file_path);
-    }
+    let client = match Client::from_config() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to set up client: {}", e);
+            process::exit(1);
+        }
+    };
 
-    let mut cmd_args = vec![];
+    let input_path = data_path("inputs", year, args.day);
 
-    if let Some(year) = args.year {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
+    if input_path.exists() && !args.refresh {
+        println!(
+            "Skipped input, \"{}\" exists (pass --refresh to replace it)",
+            input_path.display()
+        );
+    } else if let Err(e) = save_input(&client, year, args.day, args.refresh) {
+        eprintln!("Failed to download input: {}", e);
+        process::exit(1);
     }
 
-    cmd_args.append(&mut vec![
-        "--input-file".into(),
-        tmp_file_path.to_string_lossy().to_string(),
-        "--day".into(),
-        args.day.to_string(),
-        "download".into(),
-    ]);
-
-    println!("Downloading input with >aoc {}", cmd_args.join(" "));
-
-    match Command::new("aoc").args(cmd_args).output() {
-        Ok(cmd_output) => {
-            io::stdout()
-                .write_all(&cmd_output.stdout)
-                .expect("could not write cmd stdout to pipe.");
-            io::stderr()
-                .write_all(&cmd_output.stderr)
-                .expect("could not write cmd stderr to pipe.");
-            if !cmd_output.status.success() {
-                exit_with_status(1, &tmp_file_path);
+    if !args.input_only {
+        let puzzle = match client.puzzle(year, args.day) {
+            Ok(html) => Puzzle::parse(&html, client.base_url()),
+            Err(e) => {
+                eprintln!("Failed to download puzzle: {}", e);
+                process::exit(1);
             }
-        }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
-            exit_with_status(1, &tmp_file_path);
-        }
-    }
+        };
 
-    match fs::copy(&tmp_file_path, &input_path) {
-        Ok(_) => {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            exit_with_status(0, &tmp_file_path);
-        }
-        Err(e) => {
-            eprintln!("could not copy downloaded input to input file: {}", e);
-            exit_with_status(1, &tmp_file_path);
+        if let Err(e) = save_puzzle(year, args.day, &puzzle) {
+            eprintln!("Failed to write puzzle: {}", e);
+            process::exit(1);
         }
     }
+
+    println!("---");
+    println!("🎄 Successfully downloaded {} day {}.", year, args.day);
 }
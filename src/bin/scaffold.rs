@@ -2,113 +2,295 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+use advent_of_code::template::config::{default_year, parse_year};
+use advent_of_code::template::layout::{
+    bin_name, data_path, module_path, parse_bin_name, BASE_YEAR,
 };
+use advent_of_code::template::scaffold::{
+    load_template, render, replace_test_module, Action, FileOp, Placeholders,
+};
+use std::{fs, process};
 
-const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<u32> {
-    None
-}
+const EXPECTED_TEMPLATE: &str = "part_one: \npart_two: \n";
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
-}
+const REGISTRY_PATH: &str = "src/days.rs";
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+const REGISTRY_HEADER: &str = r###"/*
+ * This file is generated by `cargo scaffold`.
+ * It includes every day's binary as a module so the `advent_of_code` runner can call
+ * `part_one` / `part_two` in-process.
+ */
+#![allow(dead_code)]
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+use advent_of_code::solution;
+use advent_of_code::template::runner::Day;
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
-    }
+"###;
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
+struct Args {
+    day: u8,
+    year: Option<u16>,
+    template: Option<String>,
+    title: Option<String>,
+    answer_types: Option<(String, String)>,
+    /// Overwrite existing generated files, keeping a backup.
+    force: bool,
+    /// Only print what would be done.
+    dry_run: bool,
+    /// Only regenerate the test module of an existing day.
+    tests_only: bool,
+}
+
+/// Parses `u64` (both parts) or `u64,String` (part one, part two).
+fn parse_answer_types(value: &str) -> Result<(String, String), String> {
+    let types: Vec<&str> = value.split(',').map(str::trim).collect();
+    match types[..] {
+        [both] if !both.is_empty() => Ok((both.into(), both.into())),
+        [one, two] if !one.is_empty() && !two.is_empty() => Ok((one.into(), two.into())),
+        _ => Err(format!(
+            "\"{}\" is not \"<type>\" or \"<type>,<type>\"",
+            value
+        )),
     }
 }
-"###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
+fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+    Ok(Args {
+        year: args.opt_value_from_fn(["-y", "--year"], parse_year)?,
+        template: args.opt_value_from_str(["-t", "--template"])?,
+        title: args.opt_value_from_str("--title")?,
+        answer_types: args.opt_value_from_fn("--answer-type", parse_answer_types)?,
+        force: args.contains("--force"),
+        dry_run: args.contains("--dry-run"),
+        tests_only: args.contains("--tests-only"),
+        day: args.free_from_str()?,
+    })
 }
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+/// Whether `part` in `source` takes a second argument, the puzzle parameters.
+fn takes_params(source: &str, part: &str) -> bool {
+    let signature = format!("pub fn {}(", part);
+    source
+        .find(&signature)
+        .and_then(|start| {
+            let rest = &source[start + signature.len()..];
+            rest.find(')').map(|end| rest[..end].contains(','))
+        })
+        .unwrap_or(false)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Lists the days that have a module in `src/bin`, e.g. `src/bin/07.rs` or
+/// `src/bin/2023-07.rs`.
+fn scaffolded_days() -> Result<Vec<(u16, u8)>, std::io::Error> {
+    let mut days: Vec<(u16, u8)> = fs::read_dir("src/bin")?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            parse_bin_name(name.strip_suffix(".rs")?)
+        })
+        .collect();
+    days.sort_unstable();
+    Ok(days)
+}
+
+/// Name of the registry module of a day, e.g. `day07` or `y2023_day07`.
+fn module_name(year: u16, day: u8) -> String {
+    match year {
+        BASE_YEAR => format!("day{:02}", day),
+        year => format!("y{}_day{:02}", year, day),
+    }
+}
+
+/// Rewrites `src/days.rs` so it registers every scaffolded day.
+fn write_registry() -> Result<(), std::io::Error> {
+    let days = scaffolded_days()?;
+
+    let mut contents = REGISTRY_HEADER.to_string();
+    for &(year, day) in &days {
+        contents.push_str(&format!(
+            "#[path = \"bin/{}.rs\"]\nmod {};\n",
+            bin_name(year, day),
+            module_name(year, day)
+        ));
+    }
+    contents.push_str("\npub fn all() -> Vec<Day> {\n    vec![\n");
+    for &(year, day) in &days {
+        // days whose parts take puzzle parameters expose them as `PARAMS`
+        let source = fs::read_to_string(module_path(year, day))?;
+        let params = match (
+            takes_params(&source, "part_one"),
+            takes_params(&source, "part_two"),
+        ) {
+            (true, true) => ", params",
+            (true, false) => ", params(part_one)",
+            (false, true) => ", params(part_two)",
+            (false, false) => "",
+        };
+        let in_year = match year {
+            BASE_YEAR => String::new(),
+            year => format!(".in_year({})", year),
+        };
+        contents.push_str(&format!(
+            "        solution!({}, {}{}){},\n",
+            day,
+            module_name(year, day),
+            params,
+            in_year
+        ));
+    }
+    contents.push_str("    ]\n}\n");
+
+    fs::write(REGISTRY_PATH, contents)
 }
 
 fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(pico_args::Error::MissingArgument) => {
             eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
+            eprintln!("Options: --year <year>, --template <name>, --title <title>, --answer-type <type>[,<type>], --force, --dry-run, --tests-only");
             process::exit(1);
         }
-    };
-
-    let day_padded = format!("{:02}", day);
-
-    let input_path = format!("src/inputs/{}.txt", day_padded);
-    let example_path = format!("src/examples/{}.txt", day_padded);
-    let module_path = format!("src/bin/{}.rs", day_padded);
-
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
         Err(e) => {
-            eprintln!("Failed to create module file: {}", e);
+            eprintln!("Failed to process arguments: {}", e);
             process::exit(1);
         }
     };
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
-        Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
+    let day = args.day;
+    let year = match args.year.map_or_else(default_year, Ok) {
+        Ok(year) => year,
         Err(e) => {
-            eprintln!("Failed to write module contents: {}", e);
+            eprintln!("Failed to read config: {}", e);
             process::exit(1);
         }
+    };
+
+    let mut placeholders = Placeholders::new(year, day);
+    if let Some(title) = args.title {
+        placeholders.title = title;
+    }
+    if let Some((part_one_type, part_two_type)) = args.answer_types {
+        placeholders.part_one_type = part_one_type;
+        placeholders.part_two_type = part_two_type;
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
+    // render before creating anything, so a broken template leaves no files behind.
+    let module = match load_template(args.template.as_deref()) {
+        Ok(template) => match render(&template, &placeholders) {
+            Ok(module) => module,
+            Err(e) => {
+                eprintln!("Failed to render template: {}", e);
+                process::exit(1);
+            }
+        },
         Err(e) => {
-            eprintln!("Failed to create input file: {}", e);
+            eprintln!("Failed to load template: {}", e);
             process::exit(1);
         }
+    };
+
+    let module_path = module_path(year, day);
+    let example_path = data_path("examples", year, day);
+
+    let ops: Vec<(FileOp, Action)> = if args.tests_only {
+        let existing = match fs::read_to_string(&module_path) {
+            Ok(existing) => existing,
+            Err(e) => {
+                eprintln!(
+                    "Failed to read module file \"{}\": {}",
+                    module_path.display(),
+                    e
+                );
+                process::exit(1);
+            }
+        };
+        let contents = match replace_test_module(&existing, &module) {
+            Ok(contents) => contents,
+            Err(e) => {
+                eprintln!("Failed to regenerate tests: {}", e);
+                process::exit(1);
+            }
+        };
+
+        let op = FileOp {
+            label: "module",
+            path: module_path.clone(),
+            contents,
+            generated: true,
+        };
+        let action = op.plan(true);
+        vec![(op, action)]
+    } else {
+        [
+            FileOp {
+                label: "module",
+                path: module_path.clone(),
+                contents: module,
+                generated: true,
+            },
+            FileOp {
+                label: "input",
+                path: data_path("inputs", year, day),
+                contents: String::new(),
+                generated: false,
+            },
+            FileOp {
+                label: "example",
+                path: example_path.clone(),
+                contents: String::new(),
+                generated: false,
+            },
+            FileOp {
+                label: "expected answers",
+                path: example_path.with_extension("expected"),
+                contents: EXPECTED_TEMPLATE.to_string(),
+                generated: false,
+            },
+        ]
+        .into_iter()
+        .map(|op| {
+            let action = op.plan(args.force);
+            (op, action)
+        })
+        .collect()
+    };
+
+    if args.dry_run {
+        for (op, action) in &ops {
+            println!("{}", op.describe(action, true));
+        }
+        if !args.tests_only {
+            println!("Would register day in \"{}\"", REGISTRY_PATH);
+        }
+        return;
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    for (op, action) in &ops {
+        match op.apply(action) {
+            Ok(_) => println!("{}", op.describe(action, false)),
+            Err(e) => {
+                eprintln!("Failed to write {}: {}", op.label, e);
+                process::exit(1);
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {}", e);
-            process::exit(1);
+    }
+
+    if !args.tests_only {
+        match write_registry() {
+            Ok(_) => {
+                println!("Registered day in \"{}\"", REGISTRY_PATH);
+            }
+            Err(e) => {
+                eprintln!("Failed to update registry: {}", e);
+                process::exit(1);
+            }
         }
     }
 
     println!("---");
     println!(
         "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
+        bin_name(year, day)
     );
 }
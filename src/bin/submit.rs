@@ -0,0 +1,181 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::template::answers::Answers;
+use advent_of_code::template::client::Client;
+use advent_of_code::template::config::{default_year, parse_year};
+use advent_of_code::template::runner::{run_day, DayReport, RunOptions};
+use advent_of_code::template::submit::{Attempt, History, Reply};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[path = "../days.rs"]
+mod days;
+
+struct Args {
+    day: u8,
+    part: u8,
+    year: Option<u16>,
+    /// Submit this instead of running the solution.
+    answer: Option<String>,
+}
+
+fn parse_part(value: &str) -> Result<u8, String> {
+    match value.parse::<u8>() {
+        Ok(part) if part == 1 || part == 2 => Ok(part),
+        _ => Err(format!("\"{}\" is not a part (1 or 2)", value)),
+    }
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args.opt_value_from_fn(["-y", "--year"], parse_year)?,
+        answer: args.opt_value_from_str(["-a", "--answer"])?,
+        day: args.free_from_str()?,
+        part: args.free_from_fn(parse_part)?,
+    })
+}
+
+/// Runs the solution of `part` against the real input and returns its answer.
+fn solve(year: u16, day: u8, part: u8) -> Result<String, String> {
+    let registered = days::all()
+        .into_iter()
+        .find(|registered| (registered.year, registered.day) == (year, day))
+        .ok_or_else(|| format!("{} day {} is not scaffolded", year, day))?;
+
+    let options = RunOptions {
+        part: Some(part),
+        bench: None,
+    };
+
+    match run_day(&registered, &options) {
+        DayReport::MissingInput(e) => Err(e),
+        DayReport::Ran(results) => {
+            let result = results.first().ok_or("nothing ran")?;
+            if let Some(e) = &result.error {
+                return Err(e.to_string());
+            }
+            if let Some(panic) = &result.panic {
+                return Err(format!("panicked: {}", panic));
+            }
+            match &result.answer {
+                Some(answer) if !answer.contains('\n') => Ok(answer.clone()),
+                Some(_) => Err("the answer spans several lines, pass it with --answer".into()),
+                None => Err("the part is not solved yet".into()),
+            }
+        }
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            eprintln!("example: `cargo submit 7 1 [--year 2023] [--answer 1234]`");
+            process::exit(1);
+        }
+    };
+
+    let year = match args.year.map_or_else(default_year, Ok) {
+        Ok(year) => year,
+        Err(e) => {
+            eprintln!("Failed to read config: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let answer = match args.answer {
+        Some(answer) => answer.trim().to_string(),
+        None => match solve(year, args.day, args.part) {
+            Ok(answer) => answer,
+            Err(e) => {
+                eprintln!("Failed to solve part {}: {}", args.part, e);
+                process::exit(1);
+            }
+        },
+    };
+
+    let mut history = match History::load() {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to read history: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+
+    if let Err(reason) = history.check(year, args.day, args.part, &answer, now) {
+        eprintln!("Not submitting {}: {}", answer, reason);
+        process::exit(1);
+    }
+
+    let client = match Client::from_config() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to set up client: {}", e);
+            process::exit(1);
+        }
+    };
+
+    println!(
+        "Submitting {} for {} day {} part {}",
+        answer, year, args.day, args.part
+    );
+
+    let reply = match client.submit(year, args.day, args.part, &answer) {
+        Ok(html) => Reply::parse(&html),
+        Err(e) => {
+            eprintln!("Failed to submit: {}", e);
+            process::exit(1);
+        }
+    };
+
+    println!("{}", reply);
+    if let Some(wait) = reply.wait() {
+        println!("Next attempt possible in {}s.", wait.as_secs());
+    }
+
+    history.attempts.push(Attempt {
+        time: now,
+        year,
+        day: args.day,
+        part: args.part,
+        answer: answer.clone(),
+        reply: reply.clone(),
+    });
+
+    if let Err(e) = history.save() {
+        eprintln!("Failed to record attempt: {}", e);
+        process::exit(1);
+    }
+
+    if reply == Reply::Correct {
+        // an unreadable answers file is left alone rather than replaced by this answer only.
+        match Answers::load(year, args.day) {
+            Ok(mut answers) => {
+                let stored = match args.part {
+                    1 => &mut answers.part_one,
+                    _ => &mut answers.part_two,
+                };
+                if stored.is_none() {
+                    *stored = Some(answer);
+                    match answers.save(year, args.day) {
+                        Ok(_) => println!("Recorded answer for day {:02}", args.day),
+                        Err(e) => eprintln!("Failed to record answer: {}", e),
+                    }
+                }
+            }
+            Err(e) => eprintln!("Failed to record answer: {}", e),
+        }
+    }
+
+    if reply != Reply::Correct {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,90 @@
+/*
+ * This file is generated by `cargo scaffold`.
+ * It includes every day's binary as a module so the `advent_of_code` runner can call
+ * `part_one` / `part_two` in-process.
+ */
+#![allow(dead_code)]
+
+use advent_of_code::solution;
+use advent_of_code::template::runner::Day;
+
+#[path = "bin/01.rs"]
+mod day01;
+#[path = "bin/02.rs"]
+mod day02;
+#[path = "bin/03.rs"]
+mod day03;
+#[path = "bin/04.rs"]
+mod day04;
+#[path = "bin/05.rs"]
+mod day05;
+#[path = "bin/06.rs"]
+mod day06;
+#[path = "bin/07.rs"]
+mod day07;
+#[path = "bin/08.rs"]
+mod day08;
+#[path = "bin/09.rs"]
+mod day09;
+#[path = "bin/10.rs"]
+mod day10;
+#[path = "bin/11.rs"]
+mod day11;
+#[path = "bin/12.rs"]
+mod day12;
+#[path = "bin/13.rs"]
+mod day13;
+#[path = "bin/14.rs"]
+mod day14;
+#[path = "bin/15.rs"]
+mod day15;
+#[path = "bin/16.rs"]
+mod day16;
+#[path = "bin/17.rs"]
+mod day17;
+#[path = "bin/18.rs"]
+mod day18;
+#[path = "bin/19.rs"]
+mod day19;
+#[path = "bin/20.rs"]
+mod day20;
+#[path = "bin/21.rs"]
+mod day21;
+#[path = "bin/22.rs"]
+mod day22;
+#[path = "bin/23.rs"]
+mod day23;
+#[path = "bin/24.rs"]
+mod day24;
+#[path = "bin/25.rs"]
+mod day25;
+
+pub fn all() -> Vec<Day> {
+    vec![
+        solution!(1, day01),
+        solution!(2, day02),
+        solution!(3, day03),
+        solution!(4, day04),
+        solution!(5, day05),
+        solution!(6, day06),
+        solution!(7, day07, params),
+        solution!(8, day08),
+        solution!(9, day09),
+        solution!(10, day10),
+        solution!(11, day11),
+        solution!(12, day12),
+        solution!(13, day13),
+        solution!(14, day14),
+        solution!(15, day15, params),
+        solution!(16, day16),
+        solution!(17, day17),
+        solution!(18, day18, params(part_two)),
+        solution!(19, day19),
+        solution!(20, day20),
+        solution!(21, day21),
+        solution!(22, day22),
+        solution!(23, day23),
+        solution!(24, day24),
+        solution!(25, day25),
+    ]
+}
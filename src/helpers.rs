@@ -1,3 +1,9 @@
+pub mod direction;
+pub mod graph;
+pub mod grid;
+pub mod search;
+pub mod vector;
+
 pub fn alphabet_score(character: char) -> i32 {
     match character {
         'a' => 1,
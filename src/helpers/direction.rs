@@ -0,0 +1,97 @@
+use crate::helpers::vector::Vec2;
+
+/// One of the 4 orthogonal directions on a map printed top to bottom, so `Up` decreases `y`
+/// (the convention of [`Grid`](crate::helpers::grid::Grid)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Parses the usual spellings: `U`/`R`/`D`/`L`, `N`/`E`/`S`/`W` and `^`/`>`/`v`/`<`.
+    pub fn parse(c: char) -> Option<Self> {
+        match c {
+            'U' | 'N' | '^' => Some(Direction::Up),
+            'R' | 'E' | '>' => Some(Direction::Right),
+            'D' | 'S' | 'v' => Some(Direction::Down),
+            'L' | 'W' | '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// Turns 90° counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    /// Turns 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// A single step in this direction.
+    pub fn delta(self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(0, -1),
+            Direction::Right => Vec2::new(1, 0),
+            Direction::Down => Vec2::new(0, 1),
+            Direction::Left => Vec2::new(-1, 0),
+        }
+    }
+
+    /// [`Direction::delta`] as an offset for [`Grid`](crate::helpers::grid::Grid), e.g.
+    /// for [`Grid::ray`](crate::helpers::grid::Grid::ray).
+    pub fn offset(self) -> (isize, isize) {
+        let delta = self.delta();
+        (delta.x as isize, delta.y as isize)
+    }
+
+    /// The arrow used to draw this direction on a map.
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(direction.reverse().delta(), -direction.delta());
+            assert_eq!(Direction::parse(direction.arrow()), Some(direction));
+        }
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::parse('x'), None);
+    }
+
+    #[test]
+    fn test_delta() {
+        assert_eq!(Direction::Up.delta(), Vec2::new(0, -1));
+        assert_eq!(Direction::Right.turn_right().offset(), (0, 1));
+    }
+}
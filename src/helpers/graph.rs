@@ -0,0 +1,357 @@
+//! Small graphs with labeled nodes, e.g. valves named `"AA"`. Labels are interned to dense
+//! indices so distances fit in a matrix and sets of nodes fit in a [`BitSet`].
+
+use crate::helpers::search::bfs;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+/// Assigns each distinct label an index, counting up from 0 in order of first appearance.
+#[derive(Debug, Clone)]
+pub struct Interner<L> {
+    indices: HashMap<L, usize>,
+    labels: Vec<L>,
+}
+
+impl<L: Clone + Eq + Hash> Interner<L> {
+    pub fn new() -> Self {
+        Interner {
+            indices: HashMap::new(),
+            labels: vec![],
+        }
+    }
+
+    /// The index of `label`, assigning the next free one if it is new.
+    pub fn intern(&mut self, label: L) -> usize {
+        if let Some(&index) = self.indices.get(&label) {
+            return index;
+        }
+        self.labels.push(label.clone());
+        self.indices.insert(label, self.labels.len() - 1);
+        self.labels.len() - 1
+    }
+
+    pub fn get(&self, label: &L) -> Option<usize> {
+        self.indices.get(label).copied()
+    }
+
+    pub fn label(&self, index: usize) -> &L {
+        &self.labels[index]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+impl<L: Clone + Eq + Hash> Default for Interner<L> {
+    fn default() -> Self {
+        Interner::new()
+    }
+}
+
+/// A set of indices below 64, e.g. the valves opened so far. It is `Copy` and `Hash`, so it
+/// can be part of a memoization key without cloning a `HashSet`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitSet(pub u64);
+
+impl BitSet {
+    pub const EMPTY: BitSet = BitSet(0);
+
+    /// The set `{0, 1, ..., len - 1}`.
+    pub fn full(len: usize) -> Self {
+        assert!(len <= 64, "a BitSet holds at most 64 indices");
+        match len {
+            64 => BitSet(u64::MAX),
+            len => BitSet((1 << len) - 1),
+        }
+    }
+
+    pub fn contains(self, index: usize) -> bool {
+        index < 64 && self.0 & (1 << index) != 0
+    }
+
+    /// A copy with `index` added.
+    pub fn with(self, index: usize) -> Self {
+        assert!(index < 64, "a BitSet holds at most 64 indices");
+        BitSet(self.0 | 1 << index)
+    }
+
+    /// A copy with `index` removed.
+    pub fn without(self, index: usize) -> Self {
+        BitSet(self.0 & !(1u64.checked_shl(index as u32).unwrap_or(0)))
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        *self = self.with(index);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        *self = self.without(index);
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        BitSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        BitSet(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: Self) -> Self {
+        BitSet(self.0 & !other.0)
+    }
+
+    pub fn is_disjoint(self, other: Self) -> bool {
+        self.0 & other.0 == 0
+    }
+
+    /// The indices in the set, in ascending order.
+    pub fn iter(self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let index = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(index)
+        })
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        iter.into_iter().fold(BitSet::EMPTY, BitSet::with)
+    }
+}
+
+impl fmt::Debug for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// A directed graph over dense indices with weighted edges. Add both directions for
+/// undirected graphs.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    edges: Vec<Vec<(usize, u32)>>,
+}
+
+impl Graph {
+    pub fn new(len: usize) -> Self {
+        Graph {
+            edges: vec![vec![]; len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Adds an edge, growing the graph if `from` or `to` are new.
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: u32) {
+        let len = self.edges.len().max(from + 1).max(to + 1);
+        self.edges.resize(len, vec![]);
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn edges(&self, from: usize) -> &[(usize, u32)] {
+        &self.edges[from]
+    }
+
+    /// All-pairs distances counting every edge as one step, by a BFS from every node.
+    pub fn all_pairs_bfs(&self) -> Distances {
+        let mut distances = Distances::new(self.len());
+
+        for from in 0..self.len() {
+            let search = bfs(
+                [from],
+                |&node| self.edges[node].iter().map(|&(to, _)| to),
+                |_| false,
+            );
+            for (to, distance) in search.distances {
+                distances.set(from, to, distance as u32);
+            }
+        }
+
+        distances
+    }
+
+    /// All-pairs distances using edge weights, by Floyd–Warshall.
+    pub fn floyd_warshall(&self) -> Distances {
+        let len = self.len();
+        let mut distances = Distances::new(len);
+
+        for from in 0..len {
+            distances.set(from, from, 0);
+            for &(to, weight) in &self.edges[from] {
+                if distances.get(from, to).is_none_or(|known| weight < known) {
+                    distances.set(from, to, weight);
+                }
+            }
+        }
+
+        for via in 0..len {
+            for from in 0..len {
+                let first = match distances.get(from, via) {
+                    Some(first) => first,
+                    None => continue,
+                };
+                for to in 0..len {
+                    if let Some(second) = distances.get(via, to) {
+                        if distances
+                            .get(from, to)
+                            .is_none_or(|known| first + second < known)
+                        {
+                            distances.set(from, to, first + second);
+                        }
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+/// A matrix of shortest distances between nodes, `None` where there is no path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distances {
+    len: usize,
+    matrix: Vec<Option<u32>>,
+}
+
+impl Distances {
+    fn new(len: usize) -> Self {
+        Distances {
+            len,
+            matrix: vec![None; len * len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, from: usize, to: usize) -> Option<u32> {
+        self.matrix[from * self.len + to]
+    }
+
+    fn set(&mut self, from: usize, to: usize, distance: u32) {
+        self.matrix[from * self.len + to] = Some(distance);
+    }
+
+    /// Keeps only the distances between the nodes in `keep`, which are renumbered to their
+    /// position in `keep`. Paths still run through the dropped nodes, so this turns e.g. a
+    /// tunnel network into a complete graph of the valves worth visiting.
+    pub fn compress(&self, keep: &[usize]) -> Distances {
+        let mut compressed = Distances::new(keep.len());
+        for (i, &from) in keep.iter().enumerate() {
+            for (j, &to) in keep.iter().enumerate() {
+                compressed.matrix[i * keep.len() + j] = self.get(from, to);
+            }
+        }
+        compressed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `A - B - C - D` with a weighted shortcut `A -> D`.
+    fn graph() -> (Interner<&'static str>, Graph) {
+        let mut names = Interner::new();
+        let mut graph = Graph::default();
+
+        for (from, to) in [("A", "B"), ("B", "C"), ("C", "D")] {
+            let (from, to) = (names.intern(from), names.intern(to));
+            graph.add_edge(from, to, 1);
+            graph.add_edge(to, from, 1);
+        }
+        graph.add_edge(names.intern("A"), names.intern("D"), 5);
+
+        (names, graph)
+    }
+
+    #[test]
+    fn test_interner() {
+        let (names, _) = graph();
+        assert_eq!(names.len(), 4);
+        assert_eq!(names.get(&"C"), Some(2));
+        assert_eq!(names.get(&"E"), None);
+        assert_eq!(*names.label(3), "D");
+    }
+
+    #[test]
+    fn test_all_pairs() {
+        let (names, graph) = graph();
+        let (a, d) = (names.get(&"A").unwrap(), names.get(&"D").unwrap());
+
+        let steps = graph.all_pairs_bfs();
+        assert_eq!(steps.get(a, d), Some(1));
+        assert_eq!(steps.get(d, a), Some(3));
+        assert_eq!(steps.get(a, a), Some(0));
+
+        let weighted = graph.floyd_warshall();
+        assert_eq!(weighted.get(a, d), Some(3));
+        assert_eq!(weighted.get(d, a), Some(3));
+
+        let mut disconnected = graph.clone();
+        disconnected.add_edge(4, 0, 1);
+        assert_eq!(disconnected.floyd_warshall().get(0, 4), None);
+        assert_eq!(disconnected.all_pairs_bfs().get(4, 3), Some(2));
+    }
+
+    #[test]
+    fn test_compress() {
+        let (names, graph) = graph();
+        let keep = [names.get(&"D").unwrap(), names.get(&"B").unwrap()];
+        let compressed = graph.floyd_warshall().compress(&keep);
+
+        assert_eq!(compressed.len(), 2);
+        assert_eq!(compressed.get(0, 1), Some(2));
+        assert_eq!(compressed.get(1, 1), Some(0));
+    }
+
+    #[test]
+    fn test_bitset() {
+        let mut set: BitSet = [1, 3].into_iter().collect();
+        set.insert(63);
+        assert!(set.contains(3) && !set.contains(2) && !set.contains(64));
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1, 3, 63]);
+
+        set.remove(3);
+        assert_eq!(format!("{:?}", set), "{1, 63}");
+        assert_eq!(
+            BitSet::full(4).difference(set),
+            [0, 2, 3].into_iter().collect()
+        );
+        assert!(BitSet::full(1).is_disjoint(set));
+        assert_eq!(BitSet::full(64).len(), 64);
+        assert!(BitSet::EMPTY.is_empty());
+    }
+}
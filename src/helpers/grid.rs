@@ -0,0 +1,455 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Offsets of the 4 orthogonal neighbors, clockwise starting up. `y` grows downwards.
+pub const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the 8 surrounding cells, clockwise starting up.
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid stored row by row. Cells are addressed as `(x, y)` with `(0, 0)` in
+/// the top left corner, the way puzzle maps are printed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Panics if `cells` does not hold exactly `width * height` cells.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {}x{} grid needs {} cells",
+            width,
+            height,
+            width * height
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a character map, one row per line. `cell` returns `None` for characters
+    /// that are not allowed. Blank lines at the end of the input are ignored.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, String> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (i, line) in input.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let before = cells.len();
+            for c in line.chars() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => return Err(format!("line {}: unexpected {:?}", i + 1, c)),
+                }
+            }
+
+            let len = cells.len() - before;
+            match width {
+                Some(width) if width != len => {
+                    return Err(format!(
+                        "line {}: expected {} cells, found {}",
+                        i + 1,
+                        width,
+                        len
+                    ))
+                }
+                _ => width = Some(len),
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(x, y)` is inside the grid. Takes signed coordinates so positions just off
+    /// the edge can be tested.
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        match x < self.width && y < self.height {
+            true => Some(&self.cells[y * self.width + x]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        match x < self.width && y < self.height {
+            true => Some(&mut self.cells[y * self.width + x]),
+            false => None,
+        }
+    }
+
+    /// Like [`Grid::get`], for positions that may be negative.
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        match self.contains(x, y) {
+            true => self.get(x as usize, y as usize),
+            false => None,
+        }
+    }
+
+    /// Maps any position onto the grid as if it repeated in every direction.
+    pub fn wrap(&self, x: isize, y: isize) -> (usize, usize) {
+        (
+            x.rem_euclid(self.width as isize) as usize,
+            y.rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    /// The cell at `(x, y)` of the grid repeated in every direction. Panics on an empty grid.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let (x, y) = self.wrap(x, y);
+        &self[(x, y)]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        self[(x, y)] = value;
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell matching `predicate`, row by row.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "column {} is out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Adds a row after the last one, for grids that grow, like a tower. Panics if `row`
+    /// does not hold exactly `width` cells.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) {
+        let before = self.cells.len();
+        self.cells.extend(row);
+        assert_eq!(
+            self.cells.len() - before,
+            self.width,
+            "a row of a grid {} cells wide",
+            self.width
+        );
+        self.height += 1;
+    }
+
+    /// Removes the rows `keep` returns `false` for, moving the rows below them up.
+    pub fn retain_rows(&mut self, mut keep: impl FnMut(&[T]) -> bool) {
+        let width = self.width;
+        let mut kept = 0;
+        for y in 0..self.height {
+            if keep(&self.cells[y * width..(y + 1) * width]) {
+                if kept != y {
+                    for x in 0..width {
+                        self.cells.swap(kept * width + x, y * width + x);
+                    }
+                }
+                kept += 1;
+            }
+        }
+        self.cells.truncate(kept * width);
+        self.height = kept;
+    }
+
+    /// Positions of the orthogonal neighbors of `(x, y)` inside the grid.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_all(x, y, &OFFSETS_4)
+    }
+
+    /// Positions of the up to 8 cells surrounding `(x, y)` inside the grid.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_all(x, y, &OFFSETS_8)
+    }
+
+    fn offset_all<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(dx, dy)| self.offset(x, y, dx, dy))
+    }
+
+    /// `(x + dx, y + dy)` if it is inside the grid.
+    pub fn offset(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<(usize, usize)> {
+        let (x, y) = (x as isize + dx, y as isize + dy);
+        match self.contains(x, y) {
+            true => Some((x as usize, y as usize)),
+            false => None,
+        }
+    }
+
+    /// Positions from `(x, y)` in steps of `(dx, dy)` up to the edge, excluding `(x, y)`.
+    pub fn ray(
+        &self,
+        x: usize,
+        y: usize,
+        dx: isize,
+        dy: isize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        assert!((dx, dy) != (0, 0), "a ray needs a direction");
+        std::iter::successors(self.offset(x, y, dx, dy), move |&(x, y)| {
+            self.offset(x, y, dx, dy)
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Renders the grid with one character per cell, for types without a [`fmt::Display`]
+    /// of their own.
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        let mut output = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            output.extend(row.iter().map(&cell));
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Mirrors the grid along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    pub fn rotate_right(&self) -> Self {
+        let height = self.height;
+        self.remap(height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    /// Rotates the grid by 90° counterclockwise.
+    pub fn rotate_left(&self) -> Self {
+        let width = self.width;
+        self.remap(self.height, width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Builds a `width` by `height` grid whose cell `(x, y)` is the cell `source(x, y)` of
+    /// this one.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let cells = (0..width * height)
+            .map(|i| source(i % width, i / width))
+            .map(|(x, y)| self[(x, y)].clone())
+            .collect();
+        Grid::from_vec(width, height, cells)
+    }
+}
+
+impl Grid<char> {
+    /// Parses a character map as is.
+    pub fn from_chars(input: &str) -> Self {
+        Grid::parse(input, Some).expect("every character is a valid cell")
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is outside of the {}x{} grid",
+                x, y, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of the {}x{} grid", x, y, width, height))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#..\n.#.\n..#\n#..\n";
+
+    fn grid() -> Grid<bool> {
+        Grid::parse(MAP, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap()
+    }
+
+    fn render(grid: &Grid<bool>) -> String {
+        grid.render(|&cell| if cell { '#' } else { '.' })
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(render(&grid), MAP);
+        assert_eq!(Grid::from_chars(MAP).to_string(), MAP);
+        assert_eq!(
+            Grid::parse("#.\n#x\n", |c| (c != 'x').then_some(c)),
+            Err("line 2: unexpected 'x'".into())
+        );
+        assert_eq!(
+            Grid::parse("#.\n#\n", Some),
+            Err("line 2: expected 2 cells, found 1".into())
+        );
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = grid();
+        assert_eq!(grid.get(1, 1), Some(&true));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert!(grid[(2, 2)]);
+        assert_eq!(grid.wrap(-1, 4), (2, 0));
+        assert!(*grid.get_wrapping(3, -4));
+
+        grid.set(1, 0, true);
+        assert_eq!(grid.position(|&cell| cell), Some((0, 0)));
+        assert_eq!(grid.iter().filter(|(_, &cell)| cell).count(), 5);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbors4(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors4(1, 1).count(), 4);
+        assert_eq!(
+            grid.neighbors8(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (1, 1), (0, 1)]
+        );
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = Grid::from_chars("abc\ndef\n");
+        assert_eq!(grid.row(1).collect::<String>(), "def");
+        assert_eq!(grid.column(2).rev().collect::<String>(), "fc");
+        assert_eq!(grid.ray(0, 0, 1, 1).collect::<Vec<_>>(), vec![(1, 1)]);
+        assert_eq!(
+            grid.ray(2, 1, -1, 0)
+                .map(|position| grid[position])
+                .collect::<String>(),
+            "ed"
+        );
+        assert_eq!(grid.ray(0, 0, -1, 0).count(), 0);
+    }
+
+    #[test]
+    fn test_grow() {
+        let mut grid = Grid::new(3, 0, '.');
+        grid.push_row("#..".chars());
+        grid.push_row("...".chars());
+        grid.push_row("..#".chars());
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.to_string(), "#..\n...\n..#\n");
+
+        grid.retain_rows(|row| row.contains(&'#'));
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.to_string(), "#..\n..#\n");
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = Grid::from_chars("abc\ndef\n");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(
+            grid.map(|c| c.to_ascii_uppercase()).to_string(),
+            "ABC\nDEF\n"
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Shortest path searches over implicit graphs. Nodes are whatever the puzzle uses (grid
+//! positions, vectors, states, ...) and edges come from a `successors` closure, so no graph
+//! has to be built up front.
+//!
+//! Every search takes any number of start nodes and stops at the first node accepted by
+//! `is_target`, so "from any `a` to `E`" is a single call. Pass `|_| false` to explore
+//! everything reachable instead.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search found: the distance of every node it reached, how it got there, and the
+/// target it stopped at, if any.
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    pub distances: HashMap<N, C>,
+    /// The node each reached node was first (BFS) or best (Dijkstra, A*) reached from.
+    /// Start nodes have no predecessor.
+    pub predecessors: HashMap<N, N>,
+    pub target: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// The nodes from a start node to `node`, both included.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors.get(path.last()?) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn target_distance(&self) -> Option<C> {
+        self.distance(self.target.as_ref()?)
+    }
+
+    pub fn target_path(&self) -> Option<Vec<N>> {
+        self.path(self.target.as_ref()?)
+    }
+}
+
+/// Breadth-first search for graphs where every step costs 1.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_target: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search {
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+        target: None,
+    };
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = search.distances.entry(start.clone()) {
+            entry.insert(0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if is_target(&node) {
+            search.target = Some(node);
+            break;
+        }
+
+        let distance = search.distances[&node] + 1;
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = search.distances.entry(next.clone()) {
+                entry.insert(distance);
+                search.predecessors.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm for non-negative step costs. `successors` returns the neighbors of
+/// a node together with the cost of moving there.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_target: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_target)
+}
+
+/// A* search: Dijkstra's algorithm guided by `heuristic`, an estimate of the remaining cost
+/// to the nearest target. The result is only guaranteed to be shortest if the heuristic
+/// never overestimates, e.g. the Manhattan distance on a grid.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_target: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search {
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+        target: None,
+    };
+    // the heap holds indices into `queued`, so nodes need not be `Ord`.
+    let mut queued: Vec<(N, C)> = vec![];
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if search
+            .distances
+            .insert(start.clone(), C::default())
+            .is_none()
+        {
+            heap.push(Reverse((heuristic(&start), queued.len())));
+            queued.push((start, C::default()));
+        }
+    }
+
+    while let Some(Reverse((_, index))) = heap.pop() {
+        let (node, cost) = queued[index].clone();
+
+        // a cheaper way to this node was found after it was queued.
+        if search.distances[&node] < cost {
+            continue;
+        }
+
+        if is_target(&node) {
+            search.target = Some(node);
+            break;
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            let better = search
+                .distances
+                .get(&next)
+                .is_none_or(|&known| next_cost < known);
+
+            if better {
+                search.distances.insert(next.clone(), next_cost);
+                search.predecessors.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_cost + heuristic(&next), queued.len())));
+                queued.push((next, next_cost));
+            }
+        }
+    }
+
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small maze, `#` are walls.
+    const MAZE: [&str; 4] = ["S.#....", ".##.##.", "...#E#.", ".#....."];
+
+    fn open(x: i32, y: i32) -> bool {
+        (0..MAZE.len() as i32).contains(&y)
+            && (0..MAZE[0].len() as i32).contains(&x)
+            && MAZE[y as usize].as_bytes()[x as usize] != b'#'
+    }
+
+    fn neighbors(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
+            .into_iter()
+            .filter(|&(x, y)| open(x, y))
+            .collect()
+    }
+
+    const END: (i32, i32) = (4, 2);
+
+    #[test]
+    fn test_bfs() {
+        let search = bfs([(0, 0)], neighbors, |&node| node == END);
+
+        assert_eq!(search.target, Some(END));
+        assert_eq!(search.target_distance(), Some(8));
+
+        let path = search.target_path().unwrap();
+        assert_eq!(path.len(), 9);
+        assert_eq!((path[0], path[8]), ((0, 0), END));
+        assert!(path
+            .windows(2)
+            .all(|step| neighbors(&step[0]).contains(&step[1])));
+
+        let all = bfs([(0, 0)], neighbors, |_| false);
+        assert_eq!(all.target, None);
+        assert_eq!(all.distances.len(), 20);
+        assert_eq!(all.path(&(2, 0)), None);
+    }
+
+    #[test]
+    fn test_bfs_multi() {
+        let search = bfs([(0, 0), (6, 0)], neighbors, |&(_, y)| y == 3);
+        assert_eq!(search.target_distance(), Some(3));
+        assert_eq!(search.target_path().unwrap()[0], (0, 0));
+
+        let search = bfs([(0, 0), (6, 0)], neighbors, |&node| node == END);
+        assert_eq!(search.target_distance(), Some(6));
+        assert_eq!(search.target_path().unwrap()[0], (6, 0));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // the direct edge is cheaper in steps but not in cost.
+        let edges = |node: &char| match node {
+            'a' => vec![('b', 1), ('c', 2), ('d', 9)],
+            'b' => vec![('d', 5)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        };
+
+        let search = dijkstra(['a'], edges, |&node| node == 'd');
+        assert_eq!(search.target_distance(), Some(3));
+        assert_eq!(search.target_path(), Some(vec!['a', 'c', 'd']));
+
+        let all = dijkstra(['a'], edges, |_| false);
+        assert_eq!(all.distance(&'b'), Some(1));
+        assert_eq!(all.distance(&'d'), Some(3));
+
+        let unweighted = dijkstra(
+            [(0, 0)],
+            |node| neighbors(node).into_iter().map(|next| (next, 1)),
+            |&node| node == END,
+        );
+        assert_eq!(unweighted.target_distance(), Some(8));
+    }
+
+    #[test]
+    fn test_astar() {
+        let manhattan = |&(x, y): &(i32, i32)| (x - END.0).abs() + (y - END.1).abs();
+        let search = astar(
+            [(0, 0)],
+            |node| neighbors(node).into_iter().map(|next| (next, 1)),
+            manhattan,
+            |&node| node == END,
+        );
+
+        assert_eq!(search.target_distance(), Some(8));
+        assert_eq!(search.target_path().map(|path| path.len()), Some(9));
+        assert!(search.distances.len() < 20);
+
+        let unreachable = astar(
+            [(0, 0)],
+            |node| neighbors(node).into_iter().map(|next| (next, 1)),
+            |_| 0,
+            |&node| node == (2, 0),
+        );
+        assert_eq!(unreachable.target, None);
+    }
+}
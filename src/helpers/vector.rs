@@ -0,0 +1,264 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A 2D integer vector, used both for positions and for offsets between them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A 3D integer vector, used both for positions and for offsets between them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Vec2 { x, y }
+    }
+
+    /// Parses `x,y`, allowing whitespace around the numbers.
+    pub fn parse(input: &str) -> Option<Self> {
+        let (x, y) = input.split_once(',')?;
+        Some(Vec2::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+    }
+
+    pub fn abs(self) -> Self {
+        Vec2::new(self.x.abs(), self.y.abs())
+    }
+
+    /// Each component reduced to `-1`, `0` or `1`, i.e. a single step towards `self`.
+    pub fn signum(self) -> Self {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Distance when moving along the axes only.
+    pub fn manhattan(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Distance when diagonal moves are allowed, like a king on a chess board.
+    pub fn chebyshev(self, other: Self) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// The 4 orthogonal neighbors.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .map(move |(dx, dy)| self + Vec2::new(dx, dy))
+    }
+
+    /// The 8 surrounding positions, including diagonals.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| Vec2::new(dx, dy)))
+            .filter(|offset| *offset != Vec2::ZERO)
+            .map(move |offset| self + offset)
+    }
+}
+
+impl Vec3 {
+    pub const ZERO: Vec3 = Vec3::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    /// Parses `x,y,z`, allowing whitespace around the numbers.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut numbers = input.split(',').map(|number| number.trim().parse().ok());
+        let vector = Vec3::new(numbers.next()??, numbers.next()??, numbers.next()??);
+        match numbers.next() {
+            None => Some(vector),
+            Some(_) => None,
+        }
+    }
+
+    pub fn abs(self) -> Self {
+        Vec3::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    /// Each component reduced to `-1`, `0` or `1`, i.e. a single step towards `self`.
+    pub fn signum(self) -> Self {
+        Vec3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// Distance when moving along the axes only.
+    pub fn manhattan(self, other: Self) -> i64 {
+        let d = (self - other).abs();
+        d.x + d.y + d.z
+    }
+
+    /// Distance when diagonal moves are allowed.
+    pub fn chebyshev(self, other: Self) -> i64 {
+        let d = (self - other).abs();
+        d.x.max(d.y).max(d.z)
+    }
+
+    /// The 6 positions sharing a face.
+    pub fn neighbors6(self) -> impl Iterator<Item = Self> {
+        [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |(dx, dy, dz)| self + Vec3::new(dx, dy, dz))
+    }
+
+    /// The 26 positions sharing a face, an edge or a corner.
+    pub fn neighbors26(self) -> impl Iterator<Item = Self> {
+        (-1..=1)
+            .flat_map(|dz| {
+                (-1..=1).flat_map(move |dy| (-1..=1).map(move |dx| Vec3::new(dx, dy, dz)))
+            })
+            .filter(|offset| *offset != Vec3::ZERO)
+            .map(move |offset| self + offset)
+    }
+}
+
+/// Implements the component-wise operators for a vector type.
+macro_rules! impl_ops {
+    ($t:ident { $($c:ident),+ }) => {
+        impl Add for $t {
+            type Output = $t;
+
+            fn add(self, other: $t) -> $t {
+                $t { $($c: self.$c + other.$c),+ }
+            }
+        }
+
+        impl Sub for $t {
+            type Output = $t;
+
+            fn sub(self, other: $t) -> $t {
+                $t { $($c: self.$c - other.$c),+ }
+            }
+        }
+
+        impl Neg for $t {
+            type Output = $t;
+
+            fn neg(self) -> $t {
+                $t { $($c: -self.$c),+ }
+            }
+        }
+
+        impl Mul<i64> for $t {
+            type Output = $t;
+
+            fn mul(self, factor: i64) -> $t {
+                $t { $($c: self.$c * factor),+ }
+            }
+        }
+
+        impl AddAssign for $t {
+            fn add_assign(&mut self, other: $t) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $t {
+            fn sub_assign(&mut self, other: $t) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+impl_ops!(Vec2 { x, y });
+impl_ops!(Vec3 { x, y, z });
+
+impl From<(i64, i64)> for Vec2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Vec2::new(x, y)
+    }
+}
+
+impl From<(i64, i64, i64)> for Vec3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Vec3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_arithmetic() {
+        let mut a = Vec2::new(1, -2);
+        assert_eq!(a + Vec2::new(2, 2), Vec2::new(3, 0));
+        assert_eq!(a - Vec2::new(2, 2), Vec2::new(-1, -4));
+        assert_eq!(-a * 3, Vec2::new(-3, 6));
+        assert_eq!(Vec2::new(5, -7).signum(), Vec2::new(1, -1));
+
+        a += Vec2::new(1, 1);
+        a -= Vec2::new(0, 2);
+        assert_eq!(a, Vec2::new(2, -3));
+
+        assert_eq!(
+            Vec3::new(1, 2, 3) * 2 - Vec3::new(1, 1, 1),
+            Vec3::new(1, 3, 5)
+        );
+    }
+
+    #[test]
+    fn test_distance() {
+        let (a, b) = (Vec2::new(8, 7), Vec2::new(2, 10));
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 6);
+
+        let (a, b) = (Vec3::new(1, 2, 3), Vec3::new(-1, 2, 7));
+        assert_eq!(a.manhattan(b), 6);
+        assert_eq!(a.chebyshev(b), 4);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let origin = Vec2::ZERO;
+        assert!(origin.neighbors4().all(|n| n.manhattan(origin) == 1));
+        assert_eq!(origin.neighbors4().count(), 4);
+        assert!(origin.neighbors8().all(|n| n.chebyshev(origin) == 1));
+        assert_eq!(origin.neighbors8().collect::<HashSet<_>>().len(), 8);
+
+        let origin = Vec3::new(1, 1, 1);
+        assert!(origin.neighbors6().all(|n| n.manhattan(origin) == 1));
+        assert_eq!(origin.neighbors6().collect::<HashSet<_>>().len(), 6);
+        assert!(origin.neighbors26().all(|n| n.chebyshev(origin) == 1));
+        assert_eq!(origin.neighbors26().collect::<HashSet<_>>().len(), 26);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Vec2::parse("3, -4"), Some(Vec2::new(3, -4)));
+        assert_eq!(Vec2::parse("3"), None);
+        assert_eq!(Vec3::parse("2,2,5"), Some(Vec3::new(2, 2, 5)));
+        assert_eq!(Vec3::parse("2,2"), None);
+        assert_eq!(Vec3::parse("2,2,5,1"), None);
+        assert_eq!(Vec3::new(2, 2, 5).to_string(), "2,2,5");
+    }
+}
@@ -3,123 +3,79 @@
  * There is no need to edit this file unless you want to change template functionality.
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
-use std::env;
-use std::fs;
+use std::io;
+use template::cache::{self, Integrity};
+use template::input;
+use template::layout::BASE_YEAR;
 
 pub mod helpers;
+pub mod template;
+
+pub use template::error::Error;
+pub use template::examples::read_example;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Runs a part and prints its result.
+///
+/// Solvers that depend on puzzle parameters (e.g. a row to inspect that differs between the
+/// example and the real input) take them as a second argument: `part_one(input, &PARAMS)`
+/// is run with `solve!(1, part_one, input, &PARAMS)`.
 #[macro_export]
 macro_rules! solve {
     ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
-        use std::time::Instant;
-
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
-                    );
-                }
-                None => {
-                    println!("not solved.")
-                }
-            }
-        }
+        use advent_of_code::template::layout::parse_bin_name;
+        use advent_of_code::template::output::{print_result, OutputFormat};
+        use advent_of_code::template::runner::run_part;
 
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        let day = parse_bin_name(env!("CARGO_BIN_NAME")).map(|(_, day)| day);
+        let result = run_part(day, $part, $solver, $input);
+        print_result(&result, OutputFormat::from_env());
+    }};
+    ($part:expr, $solver:ident, $input:expr, $params:expr) => {{
+        use advent_of_code::template::layout::parse_bin_name;
+        use advent_of_code::template::output::{print_result, OutputFormat};
+        use advent_of_code::template::runner::run_part;
+
+        let day = parse_bin_name(env!("CARGO_BIN_NAME")).map(|(_, day)| day);
+        let result = run_part(day, $part, |input: &str| $solver(input, $params), $input);
+        print_result(&result, OutputFormat::from_env());
     }};
 }
 
+/// Reads `src/<folder>/<NN>.txt` of the [`BASE_YEAR`]. Panics with the path that was tried if it cannot be read.
+/// See [`template::input::resolve`] for how the real input can be overridden.
 pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-
-    let filepath = cwd.join("src").join(folder).join(format!("{:02}.txt", day));
-
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-fn parse_time(val: &str, postfix: &str) -> f64 {
-    val.split(postfix).next().unwrap().parse().unwrap()
+    read_year_file(folder, BASE_YEAR, day)
 }
 
-pub fn parse_exec_time(output: &str) -> f64 {
-    output.lines().fold(0_f64, |acc, l| {
-        if !l.contains("elapsed:") {
-            acc
-        } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
-            }
-        }
-    })
+pub fn try_read_file(folder: &str, day: u8) -> io::Result<String> {
+    try_read_year_file(folder, BASE_YEAR, day)
 }
 
-/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-#[cfg(test)]
-macro_rules! assert_approx_eq {
-    ($a:expr, $b:expr) => {{
-        let (a, b) = (&$a, &$b);
-        assert!(
-            (*a - *b).abs() < 1.0e-6,
-            "{} is not approximately equal to {}",
-            *a,
-            *b
-        );
-    }};
+/// Like [`read_file`] for a day of any year, e.g. `src/inputs/2023/01.txt`.
+pub fn read_year_file(folder: &str, year: u16, day: u8) -> String {
+    try_read_year_file(folder, year, day).unwrap_or_else(|e| panic!("{}", e))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_exec_time() {
-        assert_approx_eq!(
-            parse_exec_time(&format!(
-                "🎄 Part 1 🎄\n0 (elapsed: 74.13ns){}\n🎄 Part 2 🎄\n0 (elapsed: 50.00ns){}",
-                ANSI_RESET, ANSI_RESET
-            )),
-            0_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 755µs)\n🎄 Part 2 🎄\n0 (elapsed: 700µs)"),
-            1.455_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 70µs)\n🎄 Part 2 🎄\n0 (elapsed: 1.45ms)"),
-            1.52_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time(
-                "🎄 Part 1 🎄\n0 (elapsed: 10.3s)\n🎄 Part 2 🎄\n0 (elapsed: 100.50ms)"
-            ),
-            10400.50_f64
-        );
+/// Real inputs read from `src/inputs` are checked against the download cache, and a warning
+/// is printed if they were edited or truncated since they were downloaded.
+pub fn try_read_year_file(folder: &str, year: u16, day: u8) -> io::Result<String> {
+    let source = input::resolve(folder, year, day);
+    let contents = input::read(&source)?;
+
+    if folder == "inputs" && source == input::Source::File(input::default_path(folder, year, day)) {
+        if let Ok(Integrity::Modified(reason)) =
+            cache::Index::load().map(|index| index.verify(year, day, &contents))
+        {
+            eprintln!(
+                "Warning: the input of {} day {} differs from the download ({}), run `cargo download {} --year {} --refresh` to restore it",
+                year, day, reason, day, year
+            );
+        }
     }
+
+    Ok(contents)
 }
@@ -2,45 +2,349 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use advent_of_code::template::answers::{Answers, Verdict};
+use advent_of_code::template::baseline::Baseline;
+use advent_of_code::template::bench::BenchOptions;
+use advent_of_code::template::config::{default_year, parse_year};
+use advent_of_code::template::input;
+use advent_of_code::template::output::{
+    print_comparison, print_result, print_verdict, OutputFormat,
+};
+use advent_of_code::template::readme::{self, Progress};
+use advent_of_code::template::runner::{parse_days, run_day, Day, DayReport, RunOptions, Status};
 use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use rayon::prelude::*;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+mod days;
+
+struct Args {
+    year: Option<u16>,
+    days: Option<Vec<u8>>,
+    part: Option<u8>,
+    only_solved: bool,
+    parallel: bool,
+    bench: Option<BenchOptions>,
+    baseline: Option<PathBuf>,
+    save_baseline: Option<PathBuf>,
+    threshold: f64,
+    verify: bool,
+    record: bool,
+    readme: bool,
+    json: bool,
+}
+
+const USAGE: &str = "Usage: cargo all [--year <year>] [--days <days>] [--part <1|2>] [--only-solved] \
+[--parallel] [--json] [--input <path>] [--bench [--warmup <n>] [--runs <n>] [--budget <ms>]] \
+[--baseline <path>] [--save-baseline <path>] [--threshold <percent>] [--verify [--record]] [--readme]";
+
+fn parse_part(value: &str) -> Result<u8, String> {
+    match value.parse::<u8>() {
+        Ok(part) if part == 1 || part == 2 => Ok(part),
+        _ => Err(format!("\"{}\" is not a part (1 or 2)", value)),
+    }
+}
+
+fn parse_args(args: &mut pico_args::Arguments) -> Result<Args, pico_args::Error> {
+    let bench = if args.contains("--bench") {
+        let defaults = BenchOptions::default();
+        Some(BenchOptions {
+            warmup: args
+                .opt_value_from_str("--warmup")?
+                .unwrap_or(defaults.warmup),
+            runs: args.opt_value_from_str("--runs")?,
+            budget: args
+                .opt_value_from_str("--budget")?
+                .map_or(defaults.budget, Duration::from_millis),
+        })
+    } else {
+        None
+    };
+
+    let parsed = Args {
+        year: args.opt_value_from_fn(["-y", "--year"], parse_year)?,
+        days: args.opt_value_from_fn(["-d", "--days"], parse_days)?,
+        part: args.opt_value_from_fn(["-p", "--part"], parse_part)?,
+        only_solved: args.contains("--only-solved"),
+        parallel: args.contains("--parallel"),
+        bench,
+        baseline: args.opt_value_from_str("--baseline")?,
+        save_baseline: args.opt_value_from_str("--save-baseline")?,
+        threshold: args.opt_value_from_str("--threshold")?.unwrap_or(10_f64),
+        verify: args.contains("--verify"),
+        record: args.contains("--record"),
+        readme: args.contains("--readme"),
+        json: args.contains("--json"),
+    };
+
+    // `--input` is read by the days themselves through `read_file`, it is only consumed
+    // here so it does not count as unknown.
+    let _: Option<String> = args.opt_value_from_str("--input")?;
+
+    Ok(parsed)
+}
+
+fn print_report(day: &Day, report: &DayReport, format: OutputFormat, only_solved: bool) {
+    let is_solved = |status: Status| !only_solved || status == Status::Solved;
+
+    if only_solved {
+        match report {
+            DayReport::Ran(results) if results.iter().any(|r| r.status() == Status::Solved) => {}
+            _ => return,
+        }
+    }
+
+    if format == OutputFormat::Human {
+        println!("----------");
+        println!("{}| Day {:02} |{}", ANSI_BOLD, day.day, ANSI_RESET);
+        println!("----------");
+    }
+
+    match report {
+        DayReport::Ran(results) => {
+            for result in results.iter().filter(|result| is_solved(result.status())) {
+                print_result(result, format);
+            }
+        }
+        DayReport::MissingInput(e) => eprintln!("Not solved: {}", e),
+    }
+}
+
+/// Checks every result against `src/answers/NN.txt`, optionally storing answers that are
+/// not known yet. Returns `false` if any answer was wrong.
+fn verify(year: u16, reports: &[DayReport], format: OutputFormat, record: bool) -> bool {
+    if format == OutputFormat::Human {
+        println!("----------");
+        println!("{}| Verify |{}", ANSI_BOLD, ANSI_RESET);
+        println!("----------");
+    }
+
+    let mut counts = [0; 3];
+
+    for results in reports.iter().map(DayReport::results) {
+        let day = match results.first().and_then(|result| result.day) {
+            Some(day) => day,
+            None => continue,
+        };
+
+        let mut answers = match Answers::load(year, day) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Failed to read answers: {}", e);
+                process::exit(1);
+            }
+        };
+
+        let mut added = false;
+
+        for result in results {
+            let verdict = answers.verify(result);
+            print_verdict(result, &verdict, format);
+            counts[match verdict {
+                Verdict::Pass => 0,
+                Verdict::Fail { .. } => 1,
+                Verdict::Missing => 2,
+            }] += 1;
+
+            if record {
+                added |= answers.record(result);
+            }
+        }
+
+        if added {
+            match answers.save(year, day) {
+                Ok(_) => eprintln!("Recorded answers for day {:02}", day),
+                Err(e) => eprintln!("Failed to record answers for day {:02}: {}", day, e),
+            }
+        }
+    }
+
+    if format == OutputFormat::Human {
+        println!(
+            "{}Passed:{} {}, {}failed:{} {}, {}missing:{} {}",
+            ANSI_BOLD,
+            ANSI_RESET,
+            counts[0],
+            ANSI_BOLD,
+            ANSI_RESET,
+            counts[1],
+            ANSI_BOLD,
+            ANSI_RESET,
+            counts[2]
+        );
+    }
+
+    counts[1] == 0
+}
+
+/// Rewrites the results table of `README.md` from the reports and the stored answers.
+fn update_readme(year: u16, reports: &[DayReport]) -> io::Result<()> {
+    let mut days = vec![];
+
+    for results in reports.iter().map(DayReport::results) {
+        let day = match results.first().and_then(|result| result.day) {
+            Some(day) => day,
+            None => continue,
+        };
+        days.push(Progress::from_results(
+            day,
+            results,
+            &Answers::load(year, day)?,
+        ));
+    }
+
+    readme::update(year, &days)
+}
 
 fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{:02}", day);
+    let mut raw_args = pico_args::Arguments::from_env();
+    let args = match parse_args(&mut raw_args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
 
-            let cmd = Command::new("cargo")
-                .args(["run", "--release", "--bin", &day])
-                .output()
-                .unwrap();
+    let unknown = raw_args.finish();
+    if !unknown.is_empty() {
+        let unknown: Vec<_> = unknown.iter().map(|arg| arg.to_string_lossy()).collect();
+        eprintln!("Unknown arguments: {}", unknown.join(" "));
+        eprintln!("{}", USAGE);
+        process::exit(1);
+    }
 
-            println!("----------");
-            println!("{}| Day {} |{}", ANSI_BOLD, day, ANSI_RESET);
-            println!("----------");
+    let year = match args.year.map_or_else(default_year, Ok) {
+        Ok(year) => year,
+        Err(e) => {
+            eprintln!("Failed to read config: {}", e);
+            process::exit(1);
+        }
+    };
+
+    if args.readme && (args.days.is_some() || args.part.is_some()) {
+        eprintln!("Failed to process arguments: --readme needs all days and parts, it cannot be combined with --days or --part");
+        process::exit(1);
+    }
+
+    let format = OutputFormat::from_flag(args.json);
+    let options = RunOptions {
+        part: args.part,
+        bench: args.bench,
+    };
+
+    let days: Vec<Day> = days::all()
+        .into_iter()
+        .filter(|day| day.year == year)
+        .filter(|day| {
+            args.days
+                .as_ref()
+                .is_none_or(|days| days.contains(&day.day))
+        })
+        .collect();
+
+    if days.len() > 1 {
+        if let Some(value) = input::current_override() {
+            if let Err(e) = input::check_shared_override(&value) {
+                eprintln!("Failed to process arguments: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    // load before running, so a broken baseline file fails fast.
+    let baseline = match &args.baseline {
+        Some(path) => match Baseline::load(path) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                eprintln!("Failed to read baseline: {}", e);
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    let reports: Vec<DayReport> = if args.parallel {
+        // timings are less reliable when days compete for cores, but the report stays ordered.
+        let reports: Vec<DayReport> = days.par_iter().map(|day| run_day(day, &options)).collect();
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
+        for (day, report) in days.iter().zip(reports.iter()) {
+            print_report(day, report, format, args.only_solved);
+        }
 
+        reports
+    } else {
+        days.iter()
+            .map(|day| {
+                let report = run_day(day, &options);
+                print_report(day, &report, format, args.only_solved);
+                report
+            })
+            .collect()
+    };
+
+    let total: Duration = reports.iter().map(DayReport::elapsed).sum();
+
+    if format == OutputFormat::Human {
+        println!(
+            "{}Total:{} {}{:.2}ms{}",
+            ANSI_BOLD,
+            ANSI_RESET,
+            ANSI_ITALIC,
+            total.as_secs_f64() * 1000_f64,
+            ANSI_RESET
+        );
+    }
+
+    let verified = !(args.verify || args.record) || verify(year, &reports, format, args.record);
+
+    if args.readme {
+        match update_readme(year, &reports) {
+            Ok(_) => eprintln!("Updated \"{}\"", readme::readme_path().display()),
+            Err(e) => {
+                eprintln!("Failed to update readme: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    let results = reports.iter().flat_map(DayReport::results);
+
+    if let Some(path) = &args.save_baseline {
+        match Baseline::from_results(year, results.clone()).save(path) {
+            Ok(_) => eprintln!("Saved baseline to \"{}\"", path.display()),
+            Err(e) => {
+                eprintln!("Failed to save baseline: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    if let Some(baseline) = baseline {
+        let comparisons = baseline.compare(year, results, args.threshold);
+
+        if format == OutputFormat::Human {
+            println!("----------");
             println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
-                }
+                "{}| Baseline (threshold: {}%) |{}",
+                ANSI_BOLD, args.threshold, ANSI_RESET
             );
+            println!("----------");
+        }
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
-            }
-        })
-        .sum();
+        for comparison in &comparisons {
+            print_comparison(comparison, format);
+        }
+
+        if comparisons.iter().any(|comparison| comparison.regressed) {
+            process::exit(1);
+        }
+    }
 
-    println!(
-        "{}Total:{} {}{:.2}ms{}",
-        ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total, ANSI_RESET
-    );
+    if !verified {
+        process::exit(1);
+    }
 }
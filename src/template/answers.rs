@@ -0,0 +1,220 @@
+use crate::template::input::{default_path, write_atomic};
+use crate::template::runner::PartResult;
+use crate::try_read_year_file;
+use std::io;
+
+/// Known answers for a day's real input, stored in `src/answers/NN.txt` (or
+/// `src/answers/YYYY/NN.txt` for other years) as
+///
+/// ```text
+/// part_one: 24000
+/// part_two: 45000
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Verdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "fail",
+            Verdict::Missing => "missing",
+        }
+    }
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Self {
+        let mut answers = Answers::default();
+
+        for line in input.lines() {
+            let (key, value) = match line.split_once(':') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+            if value.is_empty() {
+                continue;
+            }
+
+            match key {
+                "part_one" => answers.part_one = Some(value.to_string()),
+                "part_two" => answers.part_two = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        answers
+    }
+
+    /// Like [`Answers::parse`], but rejects lines that are not answers, so a damaged file is
+    /// reported instead of losing those lines when it is saved again.
+    pub fn parse_strict(input: &str) -> Result<Self, String> {
+        for (i, line) in input.lines().enumerate() {
+            let key = line.split_once(':').map(|(key, _)| key.trim());
+            if !line.trim().is_empty() && !matches!(key, Some("part_one" | "part_two")) {
+                return Err(format!(
+                    "line {}: expected \"part_one: <answer>\" or \"part_two: <answer>\"",
+                    i + 1
+                ));
+            }
+        }
+
+        Ok(Answers::parse(input))
+    }
+
+    /// Loads the stored answers of `day`. A missing file means no answers are known yet.
+    pub fn load(year: u16, day: u8) -> io::Result<Self> {
+        match try_read_year_file("answers", year, day) {
+            Ok(contents) => Answers::parse_strict(&contents).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", default_path("answers", year, day).display(), e),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, year: u16, day: u8) -> io::Result<()> {
+        write_atomic(&default_path("answers", year, day), &self.to_string())
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Stores the answer of `result` unless one is already known for its part.
+    /// Returns `true` if an answer was added.
+    pub fn record(&mut self, result: &PartResult) -> bool {
+        let slot = match result.part {
+            1 => &mut self.part_one,
+            2 => &mut self.part_two,
+            _ => return false,
+        };
+
+        match (&slot, &result.answer) {
+            (None, Some(answer)) if !answer.contains('\n') => {
+                *slot = Some(answer.clone());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Takes the answers of `other` for parts that have no answer yet. Returns `true` if an
+    /// answer was added.
+    pub fn fill(&mut self, other: &Answers) -> bool {
+        let mut added = false;
+        for (slot, answer) in [
+            (&mut self.part_one, &other.part_one),
+            (&mut self.part_two, &other.part_two),
+        ] {
+            if slot.is_none() && answer.is_some() {
+                *slot = answer.clone();
+                added = true;
+            }
+        }
+        added
+    }
+
+    pub fn verify(&self, result: &PartResult) -> Verdict {
+        match (self.get(result.part), &result.answer) {
+            (None, _) => Verdict::Missing,
+            (Some(expected), Some(answer)) if expected == answer.trim() => Verdict::Pass,
+            (Some(expected), _) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "part_one: {}", self.part_one.as_deref().unwrap_or(""))?;
+        writeln!(f, "part_two: {}", self.part_two.as_deref().unwrap_or(""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn result(part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            day: Some(1),
+            part,
+            answer: answer.map(String::from),
+            error: None,
+            panic: None,
+            answer_type: "u32",
+            elapsed: Duration::ZERO,
+            stats: None,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("part_one: 24000\npart_two:\n");
+        assert_eq!(answers.get(1), Some("24000"));
+        assert_eq!(answers.get(2), None);
+        assert_eq!(Answers::parse(&answers.to_string()), answers);
+
+        assert_eq!(Answers::parse_strict(&answers.to_string()), Ok(answers));
+        assert!(Answers::parse_strict("part_one: 1\n\npart_two: 2\n").is_ok());
+        assert!(Answers::parse_strict("part_one: 1\n24000\n").is_err());
+        assert!(Answers::parse_strict("part_three: 1").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let answers = Answers::parse("part_one: CMZ");
+        assert_eq!(answers.verify(&result(1, Some("CMZ"))), Verdict::Pass);
+        assert_eq!(
+            answers.verify(&result(1, Some("MCD"))),
+            Verdict::Fail {
+                expected: "CMZ".into()
+            }
+        );
+        assert_eq!(
+            answers.verify(&result(1, None)),
+            Verdict::Fail {
+                expected: "CMZ".into()
+            }
+        );
+        assert_eq!(answers.verify(&result(2, Some("MCD"))), Verdict::Missing);
+    }
+
+    #[test]
+    fn test_record() {
+        let mut answers = Answers::parse("part_one: 1");
+        assert!(!answers.record(&result(1, Some("2"))));
+        assert!(!answers.record(&result(2, None)));
+        assert!(answers.record(&result(2, Some("3"))));
+        assert_eq!(answers.get(1), Some("1"));
+        assert_eq!(answers.get(2), Some("3"));
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut answers = Answers::parse("part_one: 1");
+        assert!(answers.fill(&Answers::parse("part_one: 2\npart_two: 3")));
+        assert_eq!(answers, Answers::parse("part_one: 1\npart_two: 3"));
+        assert!(!answers.fill(&Answers::parse("part_two: 4")));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::template::input::write_atomic;
+use crate::template::runner::{PartResult, Status};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// Stored run times, one line per part: `<year> <day> <part> <median_ns>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    times: BTreeMap<(u16, u8, u8), Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Relative change in percent. Positive values mean the part got slower.
+    pub change: f64,
+    pub regressed: bool,
+}
+
+impl Baseline {
+    /// Records the run time of every solved part in `results`, which are days of `year`.
+    pub fn from_results<'a>(year: u16, results: impl IntoIterator<Item = &'a PartResult>) -> Self {
+        let times = results
+            .into_iter()
+            .filter(|result| result.status() == Status::Solved)
+            .filter_map(|result| Some(((year, result.day?, result.part), result.time())))
+            .collect();
+
+        Baseline { times }
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut times = BTreeMap::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let parsed = match fields[..] {
+                [year, day, part, nanos] => year
+                    .parse()
+                    .ok()
+                    .zip(day.parse().ok())
+                    .zip(part.parse().ok())
+                    .zip(nanos.parse().ok()),
+                _ => None,
+            };
+
+            match parsed {
+                Some((((year, day), part), nanos)) => {
+                    times.insert((year, day, part), Duration::from_nanos(nanos));
+                }
+                None => {
+                    return Err(format!(
+                        "line {}: expected \"<year> <day> <part> <ns>\"",
+                        i + 1
+                    ))
+                }
+            }
+        }
+
+        Ok(Baseline { times })
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Baseline::parse(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        write_atomic(path, &self.to_string())
+    }
+
+    /// Compares `results`, which are days of `year`, against the baseline. Parts slower by
+    /// more than `threshold` percent are flagged as regressed. Parts missing from the
+    /// baseline, including those only recorded for another year, are skipped.
+    pub fn compare<'a>(
+        &self,
+        year: u16,
+        results: impl IntoIterator<Item = &'a PartResult>,
+        threshold: f64,
+    ) -> Vec<Comparison> {
+        Baseline::from_results(year, results)
+            .times
+            .into_iter()
+            .filter_map(|((year, day, part), current)| {
+                let baseline = *self.times.get(&(year, day, part))?;
+                let change = if baseline.is_zero() {
+                    0_f64
+                } else {
+                    (current.as_secs_f64() / baseline.as_secs_f64() - 1_f64) * 100_f64
+                };
+
+                Some(Comparison {
+                    day,
+                    part,
+                    baseline,
+                    current,
+                    change,
+                    regressed: change > threshold,
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# year day part median_ns")?;
+        for ((year, day, part), time) in &self.times {
+            writeln!(f, "{} {} {} {}", year, day, part, time.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(day: u8, part: u8, answer: Option<&str>, micros: u64) -> PartResult {
+        PartResult {
+            day: Some(day),
+            part,
+            answer: answer.map(String::from),
+            error: None,
+            panic: None,
+            answer_type: "u32",
+            elapsed: Duration::from_micros(micros),
+            stats: None,
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let results = vec![
+            result(1, 1, Some("1"), 10),
+            result(1, 2, None, 20),
+            result(12, 2, Some("2"), 3000),
+        ];
+        let baseline = Baseline::from_results(2022, &results);
+
+        assert_eq!(
+            baseline.to_string(),
+            "# year day part median_ns\n2022 1 1 10000\n2022 12 2 3000000\n"
+        );
+        assert_eq!(Baseline::parse(&baseline.to_string()), Ok(baseline));
+        assert!(Baseline::parse("2022 1 1").is_err());
+        assert!(Baseline::parse("2022 1 x 100").is_err());
+        assert!(Baseline::parse("1 1 100").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = Baseline::parse("2022 1 1 10000\n2022 1 2 10000\n2022 2 1 10000").unwrap();
+        let results = vec![
+            result(1, 1, Some("1"), 11),
+            result(1, 2, Some("1"), 12),
+            result(3, 1, Some("1"), 50),
+        ];
+
+        let comparisons = baseline.compare(2022, &results, 25_f64);
+        assert_eq!(comparisons.len(), 2);
+        assert!(!comparisons[0].regressed);
+        assert!((comparisons[0].change - 10_f64).abs() < 1e-6);
+        assert!(!comparisons[1].regressed);
+
+        let comparisons = baseline.compare(2022, &results, 15_f64);
+        assert!(!comparisons[0].regressed);
+        assert!(comparisons[1].regressed);
+
+        assert!(baseline.compare(2023, &results, 15_f64).is_empty());
+    }
+}
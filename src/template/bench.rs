@@ -0,0 +1,152 @@
+use crate::template::runner::{PartResult, Solver, Status};
+use std::time::{Duration, Instant};
+
+/// Upper bound on the number of samples taken within a time budget, so parts that run in
+/// nanoseconds do not collect millions of samples.
+const MAX_BUDGET_RUNS: u32 = 100_000;
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Untimed runs before sampling starts.
+    pub warmup: u32,
+    /// Fixed number of timed runs. If `None`, runs until `budget` is used up.
+    pub runs: Option<u32>,
+    /// Time to spend sampling a part when `runs` is not set.
+    pub budget: Duration,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: 3,
+            runs: None,
+            budget: Duration::from_millis(500),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes statistics over `samples`. Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let runs = sorted.len();
+        let nanos: Vec<f64> = sorted.iter().map(|s| s.as_nanos() as f64).collect();
+
+        let median = if runs.is_multiple_of(2) {
+            (nanos[runs / 2 - 1] + nanos[runs / 2]) / 2_f64
+        } else {
+            nanos[runs / 2]
+        };
+        let mean = nanos.iter().sum::<f64>() / runs as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / runs as f64;
+        // nearest-rank percentile.
+        let p95_rank = ((runs as f64 * 0.95).ceil() as usize).max(1);
+
+        Some(Stats {
+            runs,
+            min: sorted[0],
+            median: Duration::from_nanos(median.round() as u64),
+            mean: Duration::from_nanos(mean.round() as u64),
+            p95: sorted[p95_rank - 1],
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+}
+
+/// Runs `solver` repeatedly and returns the result of the last run, with its `stats` set.
+/// Parts that do not produce an answer are run once and returned without `stats`.
+pub fn bench(solver: Solver, input: &str, options: &BenchOptions) -> PartResult {
+    let first = solver(input);
+    if first.status() != Status::Solved {
+        return first;
+    }
+
+    for _ in 1..options.warmup {
+        solver(input);
+    }
+
+    let mut samples = vec![];
+    let started = Instant::now();
+
+    let mut result = loop {
+        let result = solver(input);
+        samples.push(result.elapsed);
+
+        let done = match options.runs {
+            Some(runs) => samples.len() >= runs as usize,
+            None => {
+                started.elapsed() >= options.budget || samples.len() >= MAX_BUDGET_RUNS as usize
+            }
+        };
+
+        if done {
+            break result;
+        }
+    };
+
+    result.stats = Stats::from_samples(&samples);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::runner::run_part;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_millis(*v)).collect()
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+
+        let stats = Stats::from_samples(&ms(&[4, 1, 3, 2])).unwrap();
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.p95, Duration::from_millis(4));
+        assert_eq!(stats.stddev, Duration::from_nanos(1_118_034));
+
+        let stats = Stats::from_samples(&ms(&[7])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.p95, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_bench_runs() {
+        let solver: Solver = |input| run_part(Some(1), 1, |i: &str| Some(i.len()), input);
+        let options = BenchOptions {
+            warmup: 1,
+            runs: Some(5),
+            budget: Duration::ZERO,
+        };
+
+        let result = bench(solver, "abc", &options);
+        assert_eq!(result.answer.as_deref(), Some("3"));
+        assert_eq!(result.stats.map(|stats| stats.runs), Some(5));
+
+        let solver: Solver = |input| run_part(Some(1), 1, |_: &str| None::<u32>, input);
+        let result = bench(solver, "abc", &options);
+        assert_eq!(result.status(), Status::Unsolved);
+        assert_eq!(result.stats, None);
+    }
+}
@@ -0,0 +1,342 @@
+use crate::template::input::write_atomic;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A downloaded input. `fetched` is in seconds since the Unix epoch, `user` is derived from
+/// the session token by [`user_key`] so the token itself is never stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub user: String,
+    pub fetched: u64,
+    pub checksum: String,
+    pub len: usize,
+}
+
+/// Metadata of every cached input, stored in `.aoc/cache/index` as one line per input:
+/// `<year> <day> <user> <fetched> <sha256> <bytes>`. The inputs themselves are kept in
+/// `.aoc/cache/<user>/<year>/NN.txt`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Index {
+    pub entries: Vec<Entry>,
+}
+
+/// Whether a working copy in `src/inputs` still is what was downloaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Integrity {
+    /// The input was not downloaded through the cache.
+    Unknown,
+    Intact,
+    /// The input differs from every download, with a guess why.
+    Modified(String),
+}
+
+fn cache_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(".aoc")
+        .join("cache")
+}
+
+pub fn index_path() -> PathBuf {
+    cache_dir().join("index")
+}
+
+pub fn cache_path(user: &str, year: u16, day: u8) -> PathBuf {
+    cache_dir()
+        .join(user)
+        .join(year.to_string())
+        .join(format!("{:02}.txt", day))
+}
+
+pub fn checksum(contents: &str) -> String {
+    Sha256::digest(contents.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// A short, stable name for the account behind `session`.
+pub fn user_key(session: &str) -> String {
+    checksum(session.trim())[..16].to_string()
+}
+
+impl Index {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut entries = vec![];
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let entry = match fields[..] {
+                [year, day, user, fetched, checksum, len] => (|| {
+                    Some(Entry {
+                        year: year.parse().ok()?,
+                        day: day.parse().ok()?,
+                        user: user.to_string(),
+                        fetched: fetched.parse().ok()?,
+                        checksum: checksum.to_string(),
+                        len: len.parse().ok()?,
+                    })
+                })(),
+                _ => None,
+            };
+
+            match entry {
+                Some(entry) => entries.push(entry),
+                None => {
+                    return Err(format!(
+                        "line {}: expected \"<year> <day> <user> <fetched> <sha256> <bytes>\"",
+                        i + 1
+                    ))
+                }
+            }
+        }
+
+        Ok(Index { entries })
+    }
+
+    /// Loads `.aoc/cache/index`. A missing file means nothing is cached yet.
+    pub fn load() -> io::Result<Self> {
+        Index::load_from(&index_path())
+    }
+
+    fn load_from(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Index::parse(&contents).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Index::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Loads the index at `path`, applies `change` and saves it again while holding a lock
+    /// on `<path>.lock`, so concurrent downloads do not drop each other's entries.
+    fn update(path: &Path, change: impl FnOnce(&mut Index)) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let lock = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path.with_extension("lock"))?;
+        lock.lock()?;
+
+        let mut index = Index::load_from(path)?;
+        change(&mut index);
+        write_atomic(path, &index.to_string())
+    }
+
+    pub fn get(&self, year: u16, day: u8, user: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| (entry.year, entry.day, entry.user.as_str()) == (year, day, user))
+    }
+
+    /// Adds `entry`, replacing an older download of the same input.
+    pub fn insert(&mut self, entry: Entry) {
+        self.entries.retain(|other| {
+            (other.year, other.day, &other.user) != (entry.year, entry.day, &entry.user)
+        });
+        self.entries.push(entry);
+        self.entries
+            .sort_by(|a, b| (a.year, a.day, &a.user).cmp(&(b.year, b.day, &b.user)));
+    }
+
+    /// Compares `contents` against every download of the input of `day`.
+    pub fn verify(&self, year: u16, day: u8, contents: &str) -> Integrity {
+        let entries: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| (entry.year, entry.day) == (year, day))
+            .collect();
+
+        if entries.is_empty() {
+            return Integrity::Unknown;
+        }
+
+        let sum = checksum(contents);
+        if entries.iter().any(|entry| entry.checksum == sum) {
+            return Integrity::Intact;
+        }
+
+        let original = entries
+            .iter()
+            .find_map(|entry| fs::read_to_string(cache_path(&entry.user, year, day)).ok());
+
+        Integrity::Modified(match original {
+            Some(original) => diagnose(&original, contents),
+            None => format!(
+                "{} bytes, downloaded were {} bytes",
+                contents.len(),
+                entries[0].len
+            ),
+        })
+    }
+}
+
+/// Guesses how `contents` came to differ from `original`.
+fn diagnose(original: &str, contents: &str) -> String {
+    if original.trim_end() == contents.trim_end() {
+        "trailing whitespace or newlines changed".into()
+    } else if original.starts_with(contents) {
+        format!(
+            "truncated to {} of {} bytes",
+            contents.len(),
+            original.len()
+        )
+    } else {
+        let line = original
+            .lines()
+            .zip(contents.lines())
+            .position(|(a, b)| a != b)
+            .unwrap_or_else(|| original.lines().count().min(contents.lines().count()));
+        format!("edited, first difference in line {}", line + 1)
+    }
+}
+
+impl fmt::Display for Index {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# year day user fetched sha256 bytes")?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{} {} {} {} {} {}",
+                entry.year, entry.day, entry.user, entry.fetched, entry.checksum, entry.len
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Stores a downloaded input in the cache and records it in the index.
+pub fn store(user: &str, year: u16, day: u8, contents: &str, fetched: u64) -> io::Result<Entry> {
+    write_atomic(&cache_path(user, year, day), contents)?;
+
+    let entry = Entry {
+        year,
+        day,
+        user: user.to_string(),
+        fetched,
+        checksum: checksum(contents),
+        len: contents.len(),
+    };
+
+    Index::update(&index_path(), |index| index.insert(entry.clone()))?;
+
+    Ok(entry)
+}
+
+/// Reads the cached input of `day` for `user`, if it was downloaded before and the cached
+/// copy is still intact.
+pub fn cached(user: &str, year: u16, day: u8) -> io::Result<Option<String>> {
+    let index = Index::load()?;
+    let entry = match index.get(year, day, user) {
+        Some(entry) => entry,
+        None => return Ok(None),
+    };
+
+    match fs::read_to_string(cache_path(user, year, day)) {
+        Ok(contents) if checksum(&contents) == entry.checksum => Ok(Some(contents)),
+        Ok(_) => Ok(None),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u8, user: &str, contents: &str) -> Entry {
+        Entry {
+            year: 2022,
+            day,
+            user: user.into(),
+            fetched: 1_670_000_000,
+            checksum: checksum(contents),
+            len: contents.len(),
+        }
+    }
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(
+            checksum("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(user_key("abc\n").len(), 16);
+        assert_eq!(user_key("abc\n"), user_key("abc"));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut index = Index::default();
+        index.insert(entry(2, "b", "2"));
+        index.insert(entry(1, "a", "1"));
+        index.insert(entry(2, "b", "22"));
+
+        assert_eq!(index.entries.len(), 2);
+        assert_eq!(index.get(2022, 2, "b").map(|entry| entry.len), Some(2));
+        assert_eq!(Index::parse(&index.to_string()), Ok(index));
+        assert!(Index::parse("2022 1 a").is_err());
+    }
+
+    #[test]
+    fn test_concurrent_update() {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-index-{}", std::process::id()));
+        let path = dir.join("index");
+
+        std::thread::scope(|scope| {
+            for day in 1..=8 {
+                let path = &path;
+                scope.spawn(move || {
+                    Index::update(path, |index| index.insert(entry(day, "a", "1"))).unwrap()
+                });
+            }
+        });
+
+        assert_eq!(Index::load_from(&path).unwrap().entries.len(), 8);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_verify() {
+        let index = Index {
+            entries: vec![entry(22, "missing-user", "1\n2\n\n10R5L\n")],
+        };
+
+        assert_eq!(index.verify(2022, 21, "x"), Integrity::Unknown);
+        assert_eq!(index.verify(2022, 22, "1\n2\n\n10R5L\n"), Integrity::Intact);
+        assert_eq!(
+            index.verify(2022, 22, "1\n2\n\n10R5L"),
+            Integrity::Modified("10 bytes, downloaded were 11 bytes".into())
+        );
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let original = "1\n2\n\n10R5L\n";
+        assert_eq!(
+            diagnose(original, "1\n2\n\n10R5L"),
+            "trailing whitespace or newlines changed"
+        );
+        assert_eq!(diagnose(original, "1\n2\n"), "truncated to 4 of 11 bytes");
+        assert_eq!(
+            diagnose(original, "1\n3\n\n10R5L\n"),
+            "edited, first difference in line 2"
+        );
+    }
+}
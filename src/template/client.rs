@@ -0,0 +1,206 @@
+use crate::template::{cache, config};
+use std::io;
+use std::time::Duration;
+
+const USER_AGENT: &str = concat!(
+    "advent_of_code template v",
+    env!("CARGO_PKG_VERSION"),
+    " (rust, ureq)"
+);
+
+/// A minimal Advent of Code client, authenticated by the session cookie.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// A client for the configured base URL and session, see [`config::base_url`] and
+    /// [`config::session`].
+    pub fn from_config() -> io::Result<Self> {
+        Ok(Client::new(&config::base_url()?, &config::session()?))
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// The account this client acts for, see [`cache::user_key`].
+    pub fn user(&self) -> String {
+        cache::user_key(&self.session)
+    }
+
+    /// Fetches the HTML of the puzzle page of `day`. With a valid session, it includes
+    /// part two once part one is solved.
+    pub fn puzzle(&self, year: u16, day: u8) -> io::Result<String> {
+        self.get(&format!("/{}/day/{}", year, day))
+    }
+
+    /// Fetches the puzzle input of `day`.
+    pub fn input(&self, year: u16, day: u8) -> io::Result<String> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    /// Posts `answer` for `part` of `day` and returns the HTML of the reply.
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> io::Result<String> {
+        let url = self.url(&format!("/{}/day/{}/answer", year, day));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        read_response("POST", &url, response)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn get(&self, path: &str) -> io::Result<String> {
+        let url = self.url(path);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        read_response("GET", &url, response)
+    }
+}
+
+fn read_response(
+    method: &str,
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> io::Result<String> {
+    match response {
+        Ok(response) => response.into_string(),
+        Err(ureq::Error::Status(status, response)) => {
+            let kind = match status {
+                400 | 401 | 403 => io::ErrorKind::PermissionDenied,
+                404 => io::ErrorKind::NotFound,
+                _ => io::ErrorKind::Other,
+            };
+            let hint = match kind {
+                io::ErrorKind::PermissionDenied => ", is the session token valid?",
+                io::ErrorKind::NotFound => ", is the puzzle unlocked yet?",
+                _ => "",
+            };
+            Err(io::Error::new(
+                kind,
+                format!(
+                    "{} {} returned {} {}{}",
+                    method,
+                    url,
+                    status,
+                    response.status_text(),
+                    hint
+                ),
+            ))
+        }
+        Err(e) => Err(io::Error::other(format!(
+            "{} {} failed: {}",
+            method, url, e
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serves `response` to a single request and returns the request head and body.
+    fn serve_once(response: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            io::Read::read_exact(&mut reader, &mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            let mut stream = stream;
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_input() {
+        let (base_url, server) = serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\n1\n2\n3\n",
+        );
+
+        let input = Client::new(&base_url, "token").input(2022, 1).unwrap();
+        assert_eq!(input, "1\n2\n3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=token\r\n"));
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, server) =
+            serve_once("HTTP/1.1 200 OK\r\nContent-Length: 4\r\nConnection: close\r\n\r\nbody");
+
+        let reply = Client::new(&base_url, "token")
+            .submit(2022, 1, 2, "24 000")
+            .unwrap();
+        assert_eq!(reply, "body");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2022/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=token\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=24+000"));
+    }
+
+    #[test]
+    fn test_input_unauthorized() {
+        let (base_url, server) = serve_once(
+            "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+
+        let e = Client::new(&base_url, "expired")
+            .input(2022, 1)
+            .unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::PermissionDenied);
+        assert!(e.to_string().contains("session token"));
+        server.join().unwrap();
+    }
+}
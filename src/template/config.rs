@@ -0,0 +1,175 @@
+use crate::template::layout::BASE_YEAR;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Environment variable that overrides the configured year, e.g. `AOC_YEAR=2023`.
+pub const YEAR_ENV: &str = "AOC_YEAR";
+
+/// Environment variable that overrides the configured base URL.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// Environment variable holding the session token. Takes precedence over `.aoc/session`.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings shared by all template binaries, stored in `.aoc/config` as
+///
+/// ```text
+/// # year used when no --year is passed
+/// year = 2023
+/// # server to download from, e.g. a local stand-in for testing
+/// base_url = "http://localhost:8080"
+/// ```
+///
+/// The session token is kept out of this file, see [`session`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    pub year: Option<u16>,
+    pub base_url: Option<String>,
+}
+
+fn aoc_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".aoc")
+}
+
+/// `.aoc/config`, anchored at the crate root.
+pub fn config_path() -> PathBuf {
+    aoc_dir().join("config")
+}
+
+/// `.aoc/session`, which holds nothing but the session token and is not committed.
+pub fn session_path() -> PathBuf {
+    aoc_dir().join("session")
+}
+
+impl Config {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut config = Config::default();
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim().trim_matches('"')),
+                None => return Err(format!("line {}: expected \"<key> = <value>\"", i + 1)),
+            };
+
+            match key {
+                "year" => {
+                    config.year =
+                        Some(parse_year(value).map_err(|e| format!("line {}: {}", i + 1, e))?)
+                }
+                "base_url" => config.base_url = Some(value.trim_end_matches('/').to_string()),
+                key => return Err(format!("line {}: unknown key \"{}\"", i + 1, key)),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Loads `.aoc/config`. A missing file means nothing is configured.
+    pub fn load() -> io::Result<Self> {
+        let path = config_path();
+        match fs::read_to_string(&path) {
+            Ok(contents) => Config::parse(&contents).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+pub fn parse_year(value: &str) -> Result<u16, String> {
+    match value.trim().parse::<u16>() {
+        Ok(year) if year >= 2015 => Ok(year),
+        _ => Err(format!(
+            "\"{}\" is not an Advent of Code year",
+            value.trim()
+        )),
+    }
+}
+
+/// The year to use when none is passed on the command line: `AOC_YEAR`, then the `year`
+/// of `.aoc/config`, then [`BASE_YEAR`].
+pub fn default_year() -> io::Result<u16> {
+    if let Some(value) = env_var(YEAR_ENV) {
+        return parse_year(&value).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e));
+    }
+
+    Ok(Config::load()?.year.unwrap_or(BASE_YEAR))
+}
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
+/// The server to talk to: `AOC_BASE_URL`, then the `base_url` of `.aoc/config`, then
+/// [`DEFAULT_BASE_URL`].
+pub fn base_url() -> io::Result<String> {
+    if let Some(value) = env_var(BASE_URL_ENV) {
+        return Ok(value.trim_end_matches('/').to_string());
+    }
+
+    Ok(Config::load()?
+        .base_url
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()))
+}
+
+/// The session token: `AOC_SESSION`, then the contents of `.aoc/session`.
+pub fn session() -> io::Result<String> {
+    if let Some(value) = env_var(SESSION_ENV) {
+        return Ok(value.trim().to_string());
+    }
+
+    let path = session_path();
+    match fs::read_to_string(&path) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        Ok(_) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("\"{}\" is empty", path.display()),
+        )),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(io::Error::new(
+            e.kind(),
+            format!(
+                "no session token, set {} or write it to \"{}\"",
+                SESSION_ENV,
+                path.display()
+            ),
+        )),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Config::parse(""), Ok(Config::default()));
+        assert_eq!(
+            Config::parse("# comment\nyear = \"2023\"\n"),
+            Ok(Config {
+                year: Some(2023),
+                base_url: None
+            })
+        );
+        assert_eq!(
+            Config::parse("base_url = http://localhost:8080/").map(|config| config.base_url),
+            Ok(Some("http://localhost:8080".into()))
+        );
+        assert!(Config::parse("year 2023").is_err());
+        assert!(Config::parse("year = 23").is_err());
+        assert!(Config::parse("years = 2023").is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt;
+
+/// An error returned by a solver, e.g. because its input could not be parsed.
+/// `day` is filled in by the runner if the solver does not set it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub day: Option<u8>,
+    /// 1-based line number of the offending input line.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        Error {
+            day: None,
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    /// Attaches the 1-based line number of the input line that caused the error.
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn with_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {:02}, ", day)?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Error::new("empty input").to_string(), "empty input");
+        assert_eq!(
+            Error::new("\"x\" is not a number")
+                .at_line(3)
+                .with_day(1)
+                .to_string(),
+            "day 01, line 3: \"x\" is not a number"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use crate::template::answers::Answers;
+use crate::template::input::default_path;
+use crate::template::runner::{run_part, IntoOutcome};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// An example input from `src/examples` (or `src/examples/YYYY` for other years): `NN.txt`
+/// or a named variant like `NN-b.txt`.
+/// Expected answers live next to it in `NN.expected` / `NN-b.expected`, in the same
+/// `part_one: ...` format as `src/answers`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// Empty for `NN.txt`, `b` for `NN-b.txt`.
+    pub name: String,
+    pub input: String,
+    pub expected: Answers,
+}
+
+fn examples_dir(year: u16) -> PathBuf {
+    let path = default_path("examples", year, 1);
+    path.parent().map(PathBuf::from).unwrap_or(path)
+}
+
+/// Path of the example `name` of `day`. An empty name is the day's default example.
+pub fn example_path(year: u16, day: u8, name: &str, extension: &str) -> PathBuf {
+    match name {
+        "" => default_path("examples", year, day).with_extension(extension),
+        name => examples_dir(year).join(format!("{:02}-{}.{}", day, name, extension)),
+    }
+}
+
+/// Reads the example `name` of `day`, e.g. `read_example(2022, 6, "b")` for
+/// `src/examples/06-b.txt`.
+pub fn read_example(year: u16, day: u8, name: &str) -> io::Result<String> {
+    let path = example_path(year, day, name, "txt");
+    fs::read_to_string(&path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("could not open \"{}\": {}", path.display(), e),
+        )
+    })
+}
+
+/// Lists the example names of `day`, the default example first.
+pub fn example_names(year: u16, day: u8) -> io::Result<Vec<String>> {
+    let prefix = format!("{:02}", day);
+
+    let mut names: Vec<String> = fs::read_dir(examples_dir(year))?
+        .filter_map(|entry| {
+            let file_name = entry.ok()?.file_name().into_string().ok()?;
+            let stem = file_name.strip_suffix(".txt")?.strip_prefix(&prefix)?;
+            match stem {
+                "" => Some(String::new()),
+                stem => stem.strip_prefix('-').map(String::from),
+            }
+        })
+        .collect();
+
+    names.sort();
+    Ok(names)
+}
+
+/// Loads every example of `day` together with its expected answers.
+pub fn examples(year: u16, day: u8) -> io::Result<Vec<Example>> {
+    example_names(year, day)?
+        .into_iter()
+        .map(|name| {
+            let input = read_example(year, day, &name)?;
+            let expected = match fs::read_to_string(example_path(year, day, &name, "expected")) {
+                Ok(contents) => Answers::parse(&contents),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Answers::default(),
+                Err(e) => return Err(e),
+            };
+            Ok(Example {
+                name,
+                input,
+                expected,
+            })
+        })
+        .collect()
+}
+
+/// Runs both parts against every example of `day` that has an expected answer and panics
+/// with a list of all mismatches. Meant to be called from a day's tests.
+pub fn check<A: IntoOutcome, B: IntoOutcome>(
+    year: u16,
+    day: u8,
+    part_one: impl Fn(&str) -> A,
+    part_two: impl Fn(&str) -> B,
+) {
+    let examples = examples(year, day).unwrap_or_else(|e| panic!("{}", e));
+    let mut failures = vec![];
+
+    for example in &examples {
+        let results = [
+            run_part(Some(day), 1, &part_one, &example.input),
+            run_part(Some(day), 2, &part_two, &example.input),
+        ];
+
+        for result in &results {
+            let expected = match example.expected.get(result.part) {
+                Some(expected) => expected,
+                None => continue,
+            };
+
+            if result.answer.as_deref().map(str::trim) != Some(expected) {
+                let path = example_path(year, day, &example.name, "txt");
+                failures.push(format!(
+                    "{} part {}: expected {}, got {}",
+                    path.file_name().unwrap_or_default().to_string_lossy(),
+                    result.part,
+                    expected,
+                    result.answer.as_deref().unwrap_or("nothing")
+                ));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!("day {:02}:\n{}", day, failures.join("\n"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::layout::BASE_YEAR;
+
+    #[test]
+    fn test_example_path() {
+        assert!(example_path(BASE_YEAR, 6, "", "txt").ends_with("src/examples/06.txt"));
+        assert!(example_path(BASE_YEAR, 6, "b", "expected").ends_with("src/examples/06-b.expected"));
+        assert!(example_path(2023, 6, "b", "txt").ends_with("src/examples/2023/06-b.txt"));
+    }
+
+    #[test]
+    fn test_examples() {
+        let examples = examples(BASE_YEAR, 9).unwrap();
+        assert_eq!(
+            examples.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(),
+            vec!["", "a"]
+        );
+        assert_eq!(examples[1].expected.get(1), Some("13"));
+    }
+
+    #[test]
+    #[should_panic(expected = "09-a.txt part 1: expected 13, got 0")]
+    fn test_check() {
+        check(BASE_YEAR, 9, |_| Some(0), |_| None::<u32>);
+    }
+}
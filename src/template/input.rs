@@ -0,0 +1,199 @@
+use crate::template::layout::data_path;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Environment variable that overrides the path of the real input, e.g.
+/// `AOC_INPUT=/data/aoc/{year}/{day}.txt` or `AOC_INPUT=-` to read from stdin.
+pub const INPUT_ENV: &str = "AOC_INPUT";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+/// [`data_path`] anchored at the crate root so it does not depend on the working directory.
+pub fn default_path(folder: &str, year: u16, day: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(data_path(folder, year, day))
+}
+
+/// Resolves where the file for `day` of `year` is read from. Only real inputs can be
+/// overridden, first by `--input <path>`, then by `AOC_INPUT`. `{year}` and `{day}` in the
+/// override are replaced with the year and the zero-padded day, `-` means stdin.
+pub fn resolve(folder: &str, year: u16, day: u8) -> Source {
+    if folder == "inputs" {
+        if let Some(value) = current_override() {
+            return source_from(&value, year, day);
+        }
+    }
+
+    Source::File(default_path(folder, year, day))
+}
+
+/// The input override of this process, if any: `--input <path>`, then `AOC_INPUT`.
+pub fn current_override() -> Option<String> {
+    input_override(env::args(), env::var(INPUT_ENV).ok())
+}
+
+/// Checks that an override can be used when several days run in one process. Stdin can
+/// only be read once and a path without `{day}` would feed the same file to every day.
+pub fn check_shared_override(value: &str) -> Result<(), String> {
+    match value {
+        "-" => Err("stdin can only be the input of a single day".into()),
+        path if !path.contains("{day}") => Err(format!(
+            "\"{}\" has no {{day}}, so every day would read the same input",
+            path
+        )),
+        _ => Ok(()),
+    }
+}
+
+pub fn read(source: &Source) -> io::Result<String> {
+    match source {
+        Source::File(path) => fs::read_to_string(path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("could not open \"{}\": {}", path.display(), e),
+            )
+        }),
+        Source::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| io::Error::new(e.kind(), format!("could not read stdin: {}", e)))?;
+            Ok(input)
+        }
+    }
+}
+
+/// Writes `contents` to a temporary file next to `path` and renames it into place, so
+/// readers never see a partial file and concurrent writers do not interleave.
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp_path = path.with_file_name(format!(
+        ".{}.{}-{}.tmp",
+        name,
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    fs::write(&tmp_path, contents)
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .map_err(|e| {
+            let _ = fs::remove_file(&tmp_path);
+            io::Error::new(
+                e.kind(),
+                format!("could not write \"{}\": {}", path.display(), e),
+            )
+        })
+}
+
+fn input_override(mut args: impl Iterator<Item = String>, var: Option<String>) -> Option<String> {
+    while let Some(arg) = args.next() {
+        if arg == "--input" {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix("--input=") {
+            return Some(value.to_string());
+        }
+    }
+
+    var.filter(|value| !value.is_empty())
+}
+
+fn source_from(value: &str, year: u16, day: u8) -> Source {
+    match value {
+        "-" => Source::Stdin,
+        path => Source::File(PathBuf::from(
+            path.replace("{year}", &year.to_string())
+                .replace("{day}", &format!("{:02}", day)),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::layout::BASE_YEAR;
+
+    fn args(values: &[&str]) -> impl Iterator<Item = String> {
+        values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_default_path() {
+        let path = default_path("examples", BASE_YEAR, 7);
+        assert!(path.is_absolute());
+        assert!(path.ends_with("src/examples/07.txt"));
+        assert!(default_path("examples", 2023, 7).ends_with("src/examples/2023/07.txt"));
+    }
+
+    #[test]
+    fn test_input_override() {
+        assert_eq!(input_override(args(&["06"]), None), None);
+        assert_eq!(
+            input_override(args(&["06", "--input", "a.txt"]), Some("b.txt".into())),
+            Some("a.txt".into())
+        );
+        assert_eq!(
+            input_override(args(&["--input=a.txt"]), None),
+            Some("a.txt".into())
+        );
+        assert_eq!(
+            input_override(args(&[]), Some("b.txt".into())),
+            Some("b.txt".into())
+        );
+        assert_eq!(input_override(args(&[]), Some("".into())), None);
+    }
+
+    #[test]
+    fn test_source_from() {
+        assert_eq!(source_from("-", 2022, 1), Source::Stdin);
+        assert_eq!(
+            source_from("/data/{year}/{day}.txt", 2022, 6),
+            Source::File(PathBuf::from("/data/2022/06.txt"))
+        );
+    }
+
+    #[test]
+    fn test_check_shared_override() {
+        assert!(check_shared_override("-").is_err());
+        assert!(check_shared_override("/data/input.txt").is_err());
+        assert_eq!(check_shared_override("/data/{year}/{day}.txt"), Ok(()));
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = env::temp_dir().join(format!("aoc-write-atomic-{}", process::id()));
+        let path = dir.join("nested").join("01.txt");
+
+        write_atomic(&path, "a").unwrap();
+        write_atomic(&path, "b").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "b");
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_error_names_path() {
+        let source = Source::File(default_path("examples", BASE_YEAR, 99));
+        let e = read(&source).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::NotFound);
+        assert!(e.to_string().contains("examples/99.txt"));
+    }
+}
@@ -0,0 +1,68 @@
+use std::path::PathBuf;
+
+/// The year this repository started with. Its days keep the flat layout
+/// (`src/bin/NN.rs`, `src/inputs/NN.txt`), days of other years live in
+/// `src/bin/YYYY-NN.rs` and `src/<folder>/YYYY/NN.txt`.
+pub const BASE_YEAR: u16 = 2022;
+
+/// Name of the binary of a day, e.g. `07` or `2023-07`.
+pub fn bin_name(year: u16, day: u8) -> String {
+    match year {
+        BASE_YEAR => format!("{:02}", day),
+        year => format!("{}-{:02}", year, day),
+    }
+}
+
+/// Parses a binary name created by [`bin_name`] back into year and day.
+pub fn parse_bin_name(name: &str) -> Option<(u16, u8)> {
+    match name.split_once('-') {
+        Some((year, day)) if day.len() == 2 => Some((year.parse().ok()?, day.parse().ok()?)),
+        None if name.len() == 2 => Some((BASE_YEAR, name.parse().ok()?)),
+        _ => None,
+    }
+}
+
+/// `src/bin/<bin_name>.rs`, relative to the crate root.
+pub fn module_path(year: u16, day: u8) -> PathBuf {
+    PathBuf::from("src")
+        .join("bin")
+        .join(format!("{}.rs", bin_name(year, day)))
+}
+
+/// `src/<folder>/NN.txt` or `src/<folder>/YYYY/NN.txt`, relative to the crate root.
+pub fn data_path(folder: &str, year: u16, day: u8) -> PathBuf {
+    let dir = PathBuf::from("src").join(folder);
+    let dir = match year {
+        BASE_YEAR => dir,
+        year => dir.join(year.to_string()),
+    };
+    dir.join(format!("{:02}.txt", day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bin_name() {
+        assert_eq!(bin_name(BASE_YEAR, 7), "07");
+        assert_eq!(bin_name(2023, 7), "2023-07");
+        assert_eq!(parse_bin_name("07"), Some((BASE_YEAR, 7)));
+        assert_eq!(parse_bin_name("2023-07"), Some((2023, 7)));
+        assert_eq!(parse_bin_name("scaffold"), None);
+        assert_eq!(parse_bin_name("2023-7"), None);
+    }
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(module_path(2023, 1), PathBuf::from("src/bin/2023-01.rs"));
+        assert_eq!(
+            data_path("inputs", BASE_YEAR, 1),
+            PathBuf::from("src/inputs/01.txt")
+        );
+        assert_eq!(
+            data_path("inputs", 2023, 1),
+            PathBuf::from("src/inputs/2023/01.txt")
+        );
+    }
+}
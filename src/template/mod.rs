@@ -0,0 +1,16 @@
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod cache;
+pub mod client;
+pub mod config;
+pub mod error;
+pub mod examples;
+pub mod input;
+pub mod layout;
+pub mod output;
+pub mod puzzle;
+pub mod readme;
+pub mod runner;
+pub mod scaffold;
+pub mod submit;
@@ -0,0 +1,262 @@
+use crate::template::answers::Verdict;
+use crate::template::baseline::Comparison;
+use crate::template::runner::PartResult;
+use crate::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::env;
+
+/// Environment variable that selects the output format, e.g. `AOC_OUTPUT=json`.
+pub const OUTPUT_ENV: &str = "AOC_OUTPUT";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Banner and ANSI-decorated timings, meant to be read by humans.
+    Human,
+    /// One JSON object per part and line, meant to be read by machines.
+    Json,
+}
+
+impl OutputFormat {
+    /// Picks the format from the `--json` flag of the current process or the `AOC_OUTPUT`
+    /// environment variable.
+    pub fn from_env() -> Self {
+        Self::from_flag(env::args().any(|arg| arg == "--json"))
+    }
+
+    /// Picks the format from an already parsed `--json` flag or the `AOC_OUTPUT` environment variable.
+    pub fn from_flag(json: bool) -> Self {
+        let var = env::var(OUTPUT_ENV).is_ok_and(|value| value.eq_ignore_ascii_case("json"));
+
+        if json || var {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Human
+        }
+    }
+}
+
+pub fn print_result(result: &PartResult, format: OutputFormat) {
+    match format {
+        OutputFormat::Human => print_human(result),
+        OutputFormat::Json => println!("{}", to_json(result)),
+    }
+}
+
+fn print_human(result: &PartResult) {
+    println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, result.part, ANSI_RESET);
+    match (&result.answer, &result.stats) {
+        (Some(answer), Some(stats)) => {
+            println!(
+                "{} {}(median: {:.2?}, mean: {:.2?}, min: {:.2?}, p95: {:.2?}, σ: {:.2?}, runs: {}){}",
+                answer,
+                ANSI_ITALIC,
+                stats.median,
+                stats.mean,
+                stats.min,
+                stats.p95,
+                stats.stddev,
+                stats.runs,
+                ANSI_RESET
+            );
+        }
+        (Some(answer), None) => {
+            println!(
+                "{} {}(elapsed: {:.2?}){}",
+                answer, ANSI_ITALIC, result.elapsed, ANSI_RESET
+            );
+        }
+        (None, _) => match (&result.error, &result.panic) {
+            (_, Some(panic)) => println!("{}panicked:{} {}", ANSI_BOLD, ANSI_RESET, panic),
+            (Some(e), None) => println!("{}error:{} {}", ANSI_BOLD, ANSI_RESET, e),
+            (None, None) => println!("not solved."),
+        },
+    }
+}
+
+/// Serializes a result as a single-line JSON object.
+pub fn to_json(result: &PartResult) -> String {
+    let day = match result.day {
+        Some(day) => day.to_string(),
+        None => "null".into(),
+    };
+    let answer = match &result.answer {
+        Some(answer) => json_string(answer),
+        None => "null".into(),
+    };
+
+    let error = match (&result.error, &result.panic) {
+        (_, Some(panic)) => json_string(&panic.to_string()),
+        (Some(e), None) => json_string(&e.to_string()),
+        (None, None) => "null".into(),
+    };
+    let stats = match &result.stats {
+        Some(stats) => format!(
+            r#","runs":{},"min_ns":{},"median_ns":{},"mean_ns":{},"p95_ns":{},"stddev_ns":{}"#,
+            stats.runs,
+            stats.min.as_nanos(),
+            stats.median.as_nanos(),
+            stats.mean.as_nanos(),
+            stats.p95.as_nanos(),
+            stats.stddev.as_nanos()
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        r#"{{"day":{},"part":{},"answer":{},"answer_type":{},"duration_ns":{},"status":{},"error":{}{}}}"#,
+        day,
+        result.part,
+        answer,
+        json_string(result.answer_type),
+        result.elapsed.as_nanos(),
+        json_string(result.status().as_str()),
+        error,
+        stats
+    )
+}
+
+pub fn print_comparison(comparison: &Comparison, format: OutputFormat) {
+    match format {
+        OutputFormat::Human => println!(
+            "Day {:02} part {}: {:.2?} -> {:.2?} ({:+.1}%){}",
+            comparison.day,
+            comparison.part,
+            comparison.baseline,
+            comparison.current,
+            comparison.change,
+            if comparison.regressed {
+                format!(" {}regressed{}", ANSI_BOLD, ANSI_RESET)
+            } else {
+                String::new()
+            }
+        ),
+        OutputFormat::Json => println!(
+            r#"{{"day":{},"part":{},"baseline_ns":{},"current_ns":{},"change_pct":{:.2},"regressed":{}}}"#,
+            comparison.day,
+            comparison.part,
+            comparison.baseline.as_nanos(),
+            comparison.current.as_nanos(),
+            comparison.change,
+            comparison.regressed
+        ),
+    }
+}
+
+pub fn print_verdict(result: &PartResult, verdict: &Verdict, format: OutputFormat) {
+    match format {
+        OutputFormat::Human => {
+            let day = result.day.map_or("??".into(), |day| format!("{:02}", day));
+            match verdict {
+                Verdict::Pass => println!("Day {} part {}: pass", day, result.part),
+                Verdict::Fail { expected } => println!(
+                    "Day {} part {}: {}fail{} (expected {}, got {})",
+                    day,
+                    result.part,
+                    ANSI_BOLD,
+                    ANSI_RESET,
+                    expected,
+                    result.answer.as_deref().unwrap_or("nothing")
+                ),
+                Verdict::Missing => println!("Day {} part {}: no stored answer", day, result.part),
+            }
+        }
+        OutputFormat::Json => {
+            let expected = match verdict {
+                Verdict::Fail { expected } => json_string(expected),
+                _ => "null".into(),
+            };
+            println!(
+                r#"{{"day":{},"part":{},"verdict":{},"expected":{}}}"#,
+                result.day.map_or("null".into(), |day| day.to_string()),
+                result.part,
+                json_string(verdict.as_str()),
+                expected
+            );
+        }
+    }
+}
+
+/// Quotes and escapes `value` as a JSON string literal.
+pub fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::bench::Stats;
+    use crate::Error;
+    use std::time::Duration;
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("abc"), r#""abc""#);
+        assert_eq!(json_string("a\"b\\c"), r#""a\"b\\c""#);
+        assert_eq!(json_string("#..\n.#.\u{1b}"), r##""#..\n.#.\u001b""##);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let solved = PartResult {
+            day: Some(5),
+            part: 1,
+            answer: Some("CMZ".into()),
+            error: None,
+            panic: None,
+            answer_type: "String",
+            elapsed: Duration::from_nanos(1234),
+            stats: None,
+        };
+        assert_eq!(
+            to_json(&solved),
+            r#"{"day":5,"part":1,"answer":"CMZ","answer_type":"String","duration_ns":1234,"status":"solved","error":null}"#
+        );
+
+        let unsolved = PartResult {
+            day: None,
+            part: 2,
+            answer: None,
+            error: None,
+            panic: None,
+            answer_type: "u32",
+            elapsed: Duration::from_nanos(10),
+            stats: None,
+        };
+        assert_eq!(
+            to_json(&unsolved),
+            r#"{"day":null,"part":2,"answer":null,"answer_type":"u32","duration_ns":10,"status":"unsolved","error":null}"#
+        );
+
+        let benchmarked = PartResult {
+            stats: Stats::from_samples(&[Duration::from_nanos(10), Duration::from_nanos(30)]),
+            ..solved.clone()
+        };
+        assert_eq!(
+            to_json(&benchmarked),
+            r#"{"day":5,"part":1,"answer":"CMZ","answer_type":"String","duration_ns":1234,"status":"solved","error":null,"runs":2,"min_ns":10,"median_ns":20,"mean_ns":20,"p95_ns":30,"stddev_ns":10}"#
+        );
+
+        let failed = PartResult {
+            answer: None,
+            error: Some(Error::new("unknown crate").at_line(2).with_day(5)),
+            ..solved
+        };
+        assert_eq!(
+            to_json(&failed),
+            r#"{"day":5,"part":1,"answer":null,"answer_type":"String","duration_ns":1234,"status":"failed","error":"day 05, line 2: unknown crate"}"#
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use crate::template::answers::Answers;
+
+/// The parts of a puzzle page that are useful offline, extracted from the HTML of
+/// `/YEAR/day/N`. Only the `<article class="day-desc">` elements are looked at: the first
+/// one describes part one, the second one (shown once part one is solved) part two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    /// E.g. `Calorie Counting`.
+    pub title: Option<String>,
+    pub markdown: String,
+    /// Contents of the first `<pre><code>` block, which usually is the example input.
+    pub example: Option<String>,
+    /// Answers for the example, taken from the last `<code><em>` of each part.
+    pub expected: Answers,
+}
+
+impl Puzzle {
+    /// `base_url` is used to make relative links absolute.
+    pub fn parse(html: &str, base_url: &str) -> Self {
+        let articles = articles(html);
+
+        let markdown = articles
+            .iter()
+            .map(|article| to_markdown(article, base_url))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let answer = |i: usize| articles.get(i).and_then(|article| expected_answer(article));
+
+        Puzzle {
+            title: articles.first().and_then(|article| title(article)),
+            markdown,
+            example: articles.first().and_then(|article| first_example(article)),
+            expected: Answers {
+                part_one: answer(0),
+                part_two: answer(1),
+            },
+        }
+    }
+}
+
+const ARTICLE_START: &str = "<article class=\"day-desc\">";
+const ARTICLE_END: &str = "</article>";
+
+/// The inner HTML of every puzzle description on the page.
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(ARTICLE_START) {
+        rest = &rest[start + ARTICLE_START.len()..];
+        let end = rest.find(ARTICLE_END).unwrap_or(rest.len());
+        articles.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+
+    articles
+}
+
+/// `Calorie Counting` from `<h2>--- Day 1: Calorie Counting ---</h2>`.
+fn title(article: &str) -> Option<String> {
+    let heading = between(article, "<h2", "</h2>")?;
+    let heading = decode_entities(&strip_tags(heading.split_once('>')?.1));
+    let title = heading.trim().trim_matches('-').trim();
+    let title = title.split_once(": ").map_or(title, |(_, title)| title);
+    Some(title.to_string())
+}
+
+fn first_example(article: &str) -> Option<String> {
+    between(article, "<pre><code>", "</code></pre>").map(|code| decode_entities(&strip_tags(code)))
+}
+
+/// The last highlighted code in a part is, by convention, the answer for the example.
+fn expected_answer(article: &str) -> Option<String> {
+    let start = article.rfind("<code><em>")? + "<code><em>".len();
+    let end = article[start..].find("</em></code>")?;
+    let answer = decode_entities(&strip_tags(&article[start..start + end]));
+    let answer = answer.trim();
+
+    match answer.is_empty() || answer.contains('\n') {
+        true => None,
+        false => Some(answer.to_string()),
+    }
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let from = text.find(start)? + start.len();
+    let to = text[from..].find(end)?;
+    Some(&text[from..from + to])
+}
+
+fn strip_tags(html: &str) -> String {
+    tokens(html)
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(text),
+            Token::Tag(_) => None,
+        })
+        .collect()
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| Some((decode_entity(&rest[1..end])?, end)));
+
+        match entity {
+            Some((character, end)) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        name => {
+            let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Text(&'a str),
+    /// Everything between `<` and `>`, e.g. `a href="/2022/about"` or `/p`.
+    Tag(&'a str),
+}
+
+fn tokens(html: &str) -> impl Iterator<Item = Token<'_>> {
+    let mut rest = html;
+
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        match rest.strip_prefix('<') {
+            Some(tag) => {
+                let end = tag.find('>').unwrap_or(tag.len());
+                rest = tag.get(end + 1..).unwrap_or("");
+                Some(Token::Tag(&tag[..end]))
+            }
+            None => {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let text = &rest[..end];
+                rest = &rest[end..];
+                Some(Token::Text(text))
+            }
+        }
+    })
+}
+
+/// Name of a tag, lowercased, and whether it closes an element.
+fn tag_name(tag: &str) -> (String, bool) {
+    let (tag, closing) = match tag.strip_prefix('/') {
+        Some(tag) => (tag, true),
+        None => (tag, false),
+    };
+    let name = tag
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or("");
+    (name.to_ascii_lowercase(), closing)
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    between(tag, &format!("{}=\"", name), "\"")
+}
+
+/// Converts the HTML of a puzzle description to Markdown. Only the elements used on puzzle
+/// pages are converted, other tags are dropped and their text is kept.
+fn to_markdown(html: &str, base_url: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut links = vec![];
+
+    for token in tokens(html) {
+        let tag = match token {
+            Token::Text(text) if in_pre => {
+                markdown.push_str(&decode_entities(text));
+                continue;
+            }
+            Token::Text(text) => {
+                let text = decode_entities(&text.replace('\n', " "));
+                // whitespace between block elements is not content
+                if !text.trim().is_empty() || !markdown.ends_with('\n') {
+                    markdown.push_str(&text);
+                }
+                continue;
+            }
+            Token::Tag(tag) => tag,
+        };
+
+        match (tag_name(tag), in_pre) {
+            // tags inside examples only highlight parts of them
+            ((name, _), true) if name != "pre" => {}
+            ((name, false), _) => match name.as_str() {
+                "h2" => markdown.push_str("## "),
+                "pre" => {
+                    in_pre = true;
+                    markdown.push_str("```\n");
+                }
+                "code" => {
+                    in_code = true;
+                    markdown.push('`');
+                }
+                "em" if !in_code => markdown.push('*'),
+                "a" => {
+                    let href = attribute(tag, "href").unwrap_or("");
+                    let href = match href.starts_with('/') {
+                        true => format!("{}{}", base_url.trim_end_matches('/'), href),
+                        false => href.to_string(),
+                    };
+                    links.push(href);
+                    markdown.push('[');
+                }
+                "li" => markdown.push_str("- "),
+                "br" => markdown.push('\n'),
+                _ => {}
+            },
+            ((name, true), _) => match name.as_str() {
+                "h2" | "p" => markdown.push_str("\n\n"),
+                "pre" => {
+                    in_pre = false;
+                    if !markdown.ends_with('\n') {
+                        markdown.push('\n');
+                    }
+                    markdown.push_str("```\n\n");
+                }
+                "code" => {
+                    in_code = false;
+                    markdown.push('`');
+                }
+                "em" if !in_code => markdown.push('*'),
+                "a" => {
+                    let href = links.pop().unwrap_or_default();
+                    markdown.push_str(&format!("]({})", href));
+                }
+                "li" => markdown.push('\n'),
+                "ul" => markdown.push('\n'),
+                _ => {}
+            },
+        }
+    }
+
+    let mut markdown: String = markdown
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    while markdown.contains("\n\n\n") {
+        markdown = markdown.replace("\n\n\n", "\n\n");
+    }
+
+    format!("{}\n", markdown.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = include_str!("fixtures/puzzle.html");
+    const MARKDOWN: &str = include_str!("fixtures/puzzle.md");
+
+    #[test]
+    fn test_parse() {
+        let puzzle = Puzzle::parse(PAGE, "https://adventofcode.com");
+
+        assert_eq!(puzzle.title.as_deref(), Some("Counting Crates"));
+        assert_eq!(puzzle.markdown, MARKDOWN);
+        assert_eq!(puzzle.example.as_deref(), Some("3\n4\n\n10\n"));
+        assert_eq!(puzzle.expected.get(1), Some("10"));
+        assert_eq!(puzzle.expected.get(2), Some("17"));
+    }
+
+    #[test]
+    fn test_parse_part_one_only() {
+        let page = &PAGE[..PAGE.find("<p>Your puzzle answer").unwrap()];
+        let puzzle = Puzzle::parse(page, "https://adventofcode.com");
+
+        assert_eq!(puzzle.expected.get(1), Some("10"));
+        assert_eq!(puzzle.expected.get(2), None);
+        assert!(!puzzle.markdown.contains("Part Two"));
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &amp;&#39;&#x41; & c"),
+            "a <b> &'A & c"
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use crate::template::answers::{Answers, Verdict};
+use crate::template::runner::{PartResult, Status};
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The generated section of `README.md` is everything between these markers.
+pub const START_MARKER: &str = "<!-- results:start -->";
+pub const END_MARKER: &str = "<!-- results:end -->";
+
+/// How far a part got, shown as one cell of the results table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    /// Solved, and the answer matches `src/answers`.
+    Star,
+    /// Solved, but there is no known answer to check it against.
+    Unverified,
+    /// Solved with an answer that differs from `src/answers`.
+    Wrong,
+    Unsolved,
+}
+
+impl Mark {
+    pub fn from_result(result: &PartResult, answers: &Answers) -> Self {
+        match (result.status(), answers.verify(result)) {
+            (Status::Solved, Verdict::Pass) => Mark::Star,
+            (Status::Solved, Verdict::Missing) => Mark::Unverified,
+            (Status::Solved, Verdict::Fail { .. }) => Mark::Wrong,
+            _ => Mark::Unsolved,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Mark::Star => "⭐",
+            Mark::Unverified => "❔",
+            Mark::Wrong => "❌",
+            Mark::Unsolved => " ",
+        }
+    }
+}
+
+/// One row of the results table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub day: u8,
+    pub parts: [Mark; 2],
+    /// Run time of the solved parts, `None` if nothing was solved.
+    pub time: Option<Duration>,
+}
+
+impl Progress {
+    pub fn from_results(day: u8, results: &[PartResult], answers: &Answers) -> Self {
+        let mark = |part: u8| {
+            results
+                .iter()
+                .find(|result| result.part == part)
+                .map_or(Mark::Unsolved, |result| Mark::from_result(result, answers))
+        };
+
+        let solved: Vec<Duration> = results
+            .iter()
+            .filter(|result| result.status() == Status::Solved)
+            .map(PartResult::time)
+            .collect();
+
+        Progress {
+            day,
+            parts: [mark(1), mark(2)],
+            time: match solved.is_empty() {
+                true => None,
+                false => Some(solved.iter().sum()),
+            },
+        }
+    }
+
+    pub fn stars(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|mark| **mark == Mark::Star)
+            .count()
+    }
+}
+
+pub fn readme_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("README.md")
+}
+
+/// Renders the heading and the table of all 25 days of `year`. Days missing from `days`
+/// get an empty row.
+pub fn render(year: u16, days: &[Progress]) -> String {
+    let stars: usize = days.iter().map(Progress::stars).sum();
+    let mut section = String::new();
+
+    writeln!(section, "## {} Results ({} / 50)\n", year, stars).unwrap();
+    writeln!(section, "| Day | Part 1 | Part 2 | Time |").unwrap();
+    writeln!(section, "| :---: | :---: | :---: | ---: |").unwrap();
+
+    for day in 1..=25 {
+        let progress = days.iter().find(|progress| progress.day == day);
+        let [one, two] = progress.map_or([Mark::Unsolved; 2], |progress| progress.parts);
+        let time = progress
+            .and_then(|progress| progress.time)
+            .map_or(String::new(), |time| format!("{:.2?}", time));
+
+        writeln!(
+            section,
+            "| [Day {}](https://adventofcode.com/{}/day/{}) | {} | {} | {} |",
+            day,
+            year,
+            day,
+            one.symbol(),
+            two.symbol(),
+            time
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        section,
+        "\n{} verified, {} solved without a known answer, {} answer differs from `src/answers`.",
+        Mark::Star.symbol(),
+        Mark::Unverified.symbol(),
+        Mark::Wrong.symbol()
+    )
+    .unwrap();
+
+    section
+}
+
+/// Replaces everything between [`START_MARKER`] and [`END_MARKER`] in `readme` with
+/// `section`, keeping the markers.
+pub fn replace_section(readme: &str, section: &str) -> Result<String, String> {
+    let start = readme
+        .find(START_MARKER)
+        .ok_or_else(|| format!("missing \"{}\"", START_MARKER))?
+        + START_MARKER.len();
+    let end = readme[start..]
+        .find(END_MARKER)
+        .ok_or_else(|| format!("missing \"{}\" after \"{}\"", END_MARKER, START_MARKER))?
+        + start;
+
+    Ok(format!(
+        "{}\n\n{}\n{}",
+        &readme[..start],
+        section.trim_end(),
+        &readme[end..]
+    ))
+}
+
+/// Rewrites the results section of `README.md`.
+pub fn update(year: u16, days: &[Progress]) -> io::Result<()> {
+    let path = readme_path();
+    let readme = fs::read_to_string(&path)?;
+    let updated = replace_section(&readme, &render(year, days)).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), e),
+        )
+    })?;
+    fs::write(path, updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(part: u8, answer: Option<&str>, micros: u64) -> PartResult {
+        PartResult {
+            day: Some(1),
+            part,
+            answer: answer.map(String::from),
+            error: None,
+            panic: None,
+            answer_type: "u32",
+            elapsed: Duration::from_micros(micros),
+            stats: None,
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let answers = Answers {
+            part_one: Some("1".into()),
+            part_two: None,
+        };
+
+        let progress = Progress::from_results(
+            1,
+            &[result(1, Some("1"), 10), result(2, Some("2"), 20)],
+            &answers,
+        );
+        assert_eq!(progress.parts, [Mark::Star, Mark::Unverified]);
+        assert_eq!(progress.time, Some(Duration::from_micros(30)));
+        assert_eq!(progress.stars(), 1);
+
+        let progress = Progress::from_results(
+            1,
+            &[result(1, Some("3"), 10), result(2, None, 20)],
+            &answers,
+        );
+        assert_eq!(progress.parts, [Mark::Wrong, Mark::Unsolved]);
+        assert_eq!(progress.time, Some(Duration::from_micros(10)));
+
+        let progress = Progress::from_results(1, &[], &answers);
+        assert_eq!(progress.parts, [Mark::Unsolved; 2]);
+        assert_eq!(progress.time, None);
+    }
+
+    #[test]
+    fn test_render() {
+        let days = [Progress {
+            day: 19,
+            parts: [Mark::Star, Mark::Star],
+            time: Some(Duration::from_micros(1500)),
+        }];
+        let section = render(2022, &days);
+
+        assert!(section.starts_with("## 2022 Results (2 / 50)\n"));
+        assert!(section
+            .contains("| [Day 19](https://adventofcode.com/2022/day/19) | ⭐ | ⭐ | 1.50ms |\n"));
+        assert!(section.contains("| [Day 23](https://adventofcode.com/2022/day/23) |   |   |  |\n"));
+        assert_eq!(section.matches("| [Day ").count(), 25);
+    }
+
+    #[test]
+    fn test_replace_section() {
+        let readme = format!("# Title\n{}\nold\n{}\nfooter\n", START_MARKER, END_MARKER);
+        assert_eq!(
+            replace_section(&readme, "new\n"),
+            Ok(format!(
+                "# Title\n{}\n\nnew\n{}\nfooter\n",
+                START_MARKER, END_MARKER
+            ))
+        );
+        assert_eq!(
+            replace_section(&replace_section(&readme, "new\n").unwrap(), "new\n"),
+            replace_section(&readme, "new\n")
+        );
+        assert!(replace_section("# Title\n", "new").is_err());
+        assert!(replace_section(START_MARKER, "new").is_err());
+    }
+}
@@ -0,0 +1,545 @@
+use crate::template::bench::{bench, BenchOptions, Stats};
+use crate::template::error::Error;
+use crate::try_read_year_file;
+use std::any::{type_name, Any};
+use std::cell::{Cell, RefCell};
+use std::fmt::{self, Display};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// The solver returned an [`Error`].
+    Failed,
+    Panicked,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Failed => "failed",
+            Status::Panicked => "panicked",
+        }
+    }
+}
+
+/// What a solver returned, with the answer already formatted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(String),
+    Unsolved,
+    Failed(Error),
+}
+
+/// Return types a solver may have: `Option<T>` where `None` means "not solved yet",
+/// `Result<T, Error>` for solvers that report invalid input, or a plain answer.
+pub trait IntoOutcome {
+    fn into_outcome(self) -> Outcome;
+
+    /// Name of the answer's type, e.g. `u32` for `Result<Option<u32>, Error>`.
+    fn answer_type() -> &'static str;
+}
+
+impl<T: Display> IntoOutcome for Option<T> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Solved(answer.to_string()),
+            None => Outcome::Unsolved,
+        }
+    }
+
+    fn answer_type() -> &'static str {
+        short_type_name::<T>()
+    }
+}
+
+impl<T: IntoOutcome> IntoOutcome for Result<T, Error> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Ok(answer) => answer.into_outcome(),
+            Err(e) => Outcome::Failed(e),
+        }
+    }
+
+    fn answer_type() -> &'static str {
+        T::answer_type()
+    }
+}
+
+macro_rules! impl_into_outcome {
+    ($($t:ty),*) => {
+        $(
+            impl IntoOutcome for $t {
+                fn into_outcome(self) -> Outcome {
+                    Outcome::Solved(self.to_string())
+                }
+
+                fn answer_type() -> &'static str {
+                    short_type_name::<$t>()
+                }
+            }
+        )*
+    };
+}
+
+impl_into_outcome!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String, char);
+
+/// The outcome of running a single part of a day's solution.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub day: Option<u8>,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub error: Option<Error>,
+    pub panic: Option<Panic>,
+    pub answer_type: &'static str,
+    pub elapsed: Duration,
+    /// Set when the part was benchmarked instead of run once.
+    pub stats: Option<Stats>,
+}
+
+impl PartResult {
+    pub fn status(&self) -> Status {
+        match (&self.answer, &self.error, &self.panic) {
+            (Some(_), _, _) => Status::Solved,
+            (None, _, Some(_)) => Status::Panicked,
+            (None, Some(_), None) => Status::Failed,
+            (None, None, None) => Status::Unsolved,
+        }
+    }
+
+    /// The representative run time: the median when benchmarked, the single run otherwise.
+    pub fn time(&self) -> Duration {
+        self.stats.map_or(self.elapsed, |stats| stats.median)
+    }
+}
+
+/// A panic caught while running a solver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panic {
+    pub message: String,
+    /// `file:line:column` of the `panic!` or failed `unwrap()`.
+    pub location: Option<String>,
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{} (at {})", self.message, location),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+thread_local! {
+    static CATCHING_PANIC: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Wraps the panic hook so panics inside [`run_part`] record their location instead of
+/// printing to stderr. Panics anywhere else still reach the previous hook.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING_PANIC.with(Cell::get) {
+                let location = info.location().map(|location| location.to_string());
+                PANIC_LOCATION.with(|cell| *cell.borrow_mut() = location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Runs `func` against `input` once and records answer and elapsed time.
+/// A panic in `func` is caught and recorded in the result.
+pub fn run_part<R: IntoOutcome>(
+    day: Option<u8>,
+    part: u8,
+    func: impl FnOnce(&str) -> R,
+    input: &str,
+) -> PartResult {
+    install_panic_hook();
+    CATCHING_PANIC.with(|cell| cell.set(true));
+
+    let timer = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| func(input)));
+    let elapsed = timer.elapsed();
+
+    CATCHING_PANIC.with(|cell| cell.set(false));
+
+    let outcome = match result {
+        Ok(result) => Ok(result.into_outcome()),
+        Err(payload) => Err(Panic {
+            message: panic_message(payload.as_ref()),
+            location: PANIC_LOCATION.with(|cell| cell.borrow_mut().take()),
+        }),
+    };
+
+    let (answer, error, panic) = match outcome {
+        Ok(Outcome::Solved(answer)) => (Some(answer), None, None),
+        Ok(Outcome::Unsolved) => (None, None, None),
+        Ok(Outcome::Failed(e)) => match (e.day, day) {
+            (None, Some(day)) => (None, Some(e.with_day(day)), None),
+            _ => (None, Some(e), None),
+        },
+        Err(panic) => (None, None, Some(panic)),
+    };
+
+    PartResult {
+        day,
+        part,
+        answer,
+        error,
+        panic,
+        answer_type: R::answer_type(),
+        elapsed,
+        stats: None,
+    }
+}
+
+/// A part of a solution, with its answer type erased so all days fit into one registry.
+pub type Solver = fn(&str) -> PartResult;
+
+/// A registered day. Use [`solution!`](crate::solution) to create one from a day's module.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub part_one: Solver,
+    pub part_two: Solver,
+}
+
+impl Day {
+    /// Moves a day created by [`solution!`](crate::solution) out of the
+    /// [`BASE_YEAR`](crate::template::layout::BASE_YEAR).
+    pub fn in_year(self, year: u16) -> Self {
+        Day { year, ..self }
+    }
+}
+
+/// Creates a [`Day`] from a module exposing `part_one` and `part_two`.
+/// Pass `params` for modules whose parts take their real-input `PARAMS` as second argument,
+/// or `params(part_one)` / `params(part_two)` if only one of them does.
+#[macro_export]
+macro_rules! solution {
+    (@day $day:expr, $module:ident, part_one($($one:expr)?), part_two($($two:expr)?)) => {
+        $crate::template::runner::Day {
+            year: $crate::template::layout::BASE_YEAR,
+            day: $day,
+            part_one: |input| {
+                $crate::template::runner::run_part(
+                    Some($day),
+                    1,
+                    |input| $module::part_one(input $(, $one)?),
+                    input,
+                )
+            },
+            part_two: |input| {
+                $crate::template::runner::run_part(
+                    Some($day),
+                    2,
+                    |input| $module::part_two(input $(, $two)?),
+                    input,
+                )
+            },
+        }
+    };
+    ($day:expr, $module:ident) => {
+        $crate::solution!(@day $day, $module, part_one(), part_two())
+    };
+    ($day:expr, $module:ident, params) => {
+        $crate::solution!(
+            @day $day,
+            $module,
+            part_one(&$module::PARAMS),
+            part_two(&$module::PARAMS)
+        )
+    };
+    ($day:expr, $module:ident, params(part_one)) => {
+        $crate::solution!(@day $day, $module, part_one(&$module::PARAMS), part_two())
+    };
+    ($day:expr, $module:ident, params(part_two)) => {
+        $crate::solution!(@day $day, $module, part_one(), part_two(&$module::PARAMS))
+    };
+}
+
+#[derive(Debug, Clone)]
+pub enum DayReport {
+    Ran(Vec<PartResult>),
+    MissingInput(String),
+}
+
+impl DayReport {
+    /// Results of the parts that ran, empty if the day could not run.
+    pub fn results(&self) -> &[PartResult] {
+        match self {
+            DayReport::Ran(results) => results,
+            _ => &[],
+        }
+    }
+
+    /// Sum of the run time of all solved parts.
+    pub fn elapsed(&self) -> Duration {
+        match self {
+            DayReport::Ran(results) => results
+                .iter()
+                .filter(|result| result.status() == Status::Solved)
+                .map(|result| result.time())
+                .sum(),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunOptions {
+    /// Only run this part. Runs both parts if `None`.
+    pub part: Option<u8>,
+    /// Benchmark each part instead of running it once.
+    pub bench: Option<BenchOptions>,
+}
+
+/// Reads the day's input and runs the selected parts in-process.
+pub fn run_day(day: &Day, options: &RunOptions) -> DayReport {
+    let input = match try_read_year_file("inputs", day.year, day.day) {
+        Ok(input) => input,
+        Err(e) => return DayReport::MissingInput(e.to_string()),
+    };
+
+    let solvers = [(1, day.part_one), (2, day.part_two)];
+
+    let results = solvers
+        .iter()
+        .filter(|(number, _)| options.part.is_none_or(|part| part == *number))
+        .map(|(_, solver)| match &options.bench {
+            Some(bench_options) => bench(*solver, &input, bench_options),
+            None => solver(&input),
+        })
+        .collect();
+
+    DayReport::Ran(results)
+}
+
+/// Parses a day selection such as `3,5-9` into a sorted list of days.
+pub fn parse_days(value: &str) -> Result<Vec<u8>, String> {
+    let mut days = vec![];
+
+    for item in value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        let parse = |day: &str| -> Result<u8, String> {
+            match day.trim().parse::<u8>() {
+                Ok(day) if (1..=25).contains(&day) => Ok(day),
+                _ => Err(format!("\"{}\" is not a day between 1 and 25", day.trim())),
+            }
+        };
+
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(format!("\"{}\" is not an ascending range", item));
+                }
+                days.extend(start..=end);
+            }
+            None => days.push(parse(item)?),
+        }
+    }
+
+    if days.is_empty() {
+        return Err("no days selected".into());
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+/// Extracts the message of a panic payload, which is a `&str` or `String` for `panic!` calls.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".into()
+    }
+}
+
+/// `type_name` without the module path, e.g. `String` instead of `alloc::string::String`.
+fn short_type_name<T>() -> &'static str {
+    let name = type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_part() {
+        let result = run_part(Some(1), 2, |input| Some(input.len()), "abc");
+        assert_eq!(result.day, Some(1));
+        assert_eq!(result.part, 2);
+        assert_eq!(result.answer.as_deref(), Some("3"));
+        assert_eq!(result.answer_type, "usize");
+        assert_eq!(result.status(), Status::Solved);
+
+        let result = run_part(None, 1, |_| None::<String>, "");
+        assert_eq!(result.answer, None);
+        assert_eq!(result.answer_type, "String");
+        assert_eq!(result.status(), Status::Unsolved);
+    }
+
+    #[test]
+    fn test_run_part_result() {
+        let result = run_part(
+            Some(1),
+            1,
+            |input| {
+                input
+                    .parse::<u64>()
+                    .map_err(|_| Error::new("nan").at_line(1))
+            },
+            "12",
+        );
+        assert_eq!(result.answer.as_deref(), Some("12"));
+        assert_eq!(result.answer_type, "u64");
+        assert_eq!(result.status(), Status::Solved);
+
+        let result = run_part(
+            Some(1),
+            1,
+            |input| {
+                input
+                    .parse::<u64>()
+                    .map_err(|_| Error::new("nan").at_line(1))
+            },
+            "x",
+        );
+        assert_eq!(result.answer, None);
+        assert_eq!(result.error, Some(Error::new("nan").at_line(1).with_day(1)));
+        assert_eq!(result.status(), Status::Failed);
+
+        let result = run_part(None, 2, |_| Ok::<Option<i32>, Error>(None), "");
+        assert_eq!(result.answer_type, "i32");
+        assert_eq!(result.status(), Status::Unsolved);
+    }
+
+    mod day00 {
+        pub fn part_one(input: &str) -> Option<usize> {
+            Some(input.len())
+        }
+
+        pub fn part_two(_input: &str) -> Option<usize> {
+            panic!("part two exploded")
+        }
+    }
+
+    mod day99 {
+        pub struct Params {
+            pub factor: usize,
+        }
+
+        pub const PARAMS: Params = Params { factor: 10 };
+
+        pub fn part_one(input: &str, params: &Params) -> Option<usize> {
+            Some(input.len() * params.factor)
+        }
+
+        pub fn part_two(_input: &str, _params: &Params) -> Option<usize> {
+            None
+        }
+    }
+
+    mod day98 {
+        pub use super::day99::{Params, PARAMS};
+
+        pub fn part_one(input: &str) -> Option<usize> {
+            Some(input.len())
+        }
+
+        pub fn part_two(input: &str, params: &Params) -> Option<usize> {
+            Some(input.len() + params.factor)
+        }
+    }
+
+    #[test]
+    fn test_solution_params() {
+        let day = crate::solution!(99, day99, params);
+        assert_eq!((day.part_one)("abcd").answer.as_deref(), Some("40"));
+        assert_eq!((day.part_two)("abcd").status(), Status::Unsolved);
+
+        let day = crate::solution!(98, day98, params(part_two));
+        assert_eq!((day.part_one)("abcd").answer.as_deref(), Some("4"));
+        assert_eq!((day.part_two)("abcd").answer.as_deref(), Some("14"));
+    }
+
+    #[test]
+    fn test_solution() {
+        let day = crate::solution!(0, day00);
+        assert_eq!(day.day, 0);
+        assert_eq!(day.year, crate::template::layout::BASE_YEAR);
+        assert_eq!(day.in_year(2023).year, 2023);
+
+        let result = (day.part_one)("abcd");
+        assert_eq!(result.day, Some(0));
+        assert_eq!(result.part, 1);
+        assert_eq!(result.answer.as_deref(), Some("4"));
+    }
+
+    #[test]
+    fn test_run_part_panic() {
+        let result = run_part(Some(0), 2, day00::part_two, "");
+        assert_eq!(result.status(), Status::Panicked);
+        assert_eq!(result.answer_type, "usize");
+
+        let panic = result.panic.unwrap();
+        assert_eq!(panic.message, "part two exploded");
+        assert!(panic.location.unwrap().contains("runner.rs"));
+
+        // the hook only swallows panics inside `run_part`.
+        assert!(!CATCHING_PANIC.with(Cell::get));
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("3"), Ok(vec![3]));
+        assert_eq!(parse_days("3,5-9"), Ok(vec![3, 5, 6, 7, 8, 9]));
+        assert_eq!(parse_days("9-10, 1,10"), Ok(vec![1, 9, 10]));
+        assert!(parse_days("").is_err());
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("9-5").is_err());
+        assert!(parse_days("a").is_err());
+    }
+
+    #[test]
+    fn test_run_day_missing_input() {
+        let day = crate::solution!(0, day00);
+        assert!(matches!(
+            run_day(&day, &RunOptions::default()),
+            DayReport::MissingInput(_)
+        ));
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let payload = panic::catch_unwind(|| panic!("static message")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "static message");
+
+        let payload = panic::catch_unwind(|| panic!("formatted {}", 42)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "formatted 42");
+    }
+}
@@ -0,0 +1,364 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Module template used when `.aoc/templates` has no `default.rs`.
+pub const DEFAULT_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<{{part_one_type}}> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{part_two_type}}> {
+    None
+}
+
+fn main() {
+    let input = &advent_of_code::read_year_file("inputs", {{year}}, {{day}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_year_file("examples", {{year}}, {{day}});
+        assert_eq!(part_one(&input), None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_year_file("examples", {{year}}, {{day}});
+        assert_eq!(part_two(&input), None);
+    }
+
+    #[test]
+    fn test_examples() {
+        advent_of_code::template::examples::check({{year}}, {{day}}, part_one, part_two);
+    }
+}
+"###;
+
+/// Values substituted into a module template:
+///
+/// | placeholder         | example              |
+/// | ------------------- | -------------------- |
+/// | `{{year}}`          | `2022`               |
+/// | `{{day}}`           | `7`                  |
+/// | `{{day_padded}}`    | `07`                 |
+/// | `{{title}}`         | `Camp Cleanup`       |
+/// | `{{part_one_type}}` | `u32`                |
+/// | `{{part_two_type}}` | `u32`                |
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholders {
+    pub year: u16,
+    pub day: u8,
+    pub title: String,
+    pub part_one_type: String,
+    pub part_two_type: String,
+}
+
+impl Placeholders {
+    /// Placeholders with the title `Day N` and `u32` answers.
+    pub fn new(year: u16, day: u8) -> Self {
+        Placeholders {
+            year,
+            day,
+            title: format!("Day {}", day),
+            part_one_type: "u32".into(),
+            part_two_type: "u32".into(),
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<String> {
+        match name {
+            "year" => Some(self.year.to_string()),
+            "day" => Some(self.day.to_string()),
+            "day_padded" => Some(format!("{:02}", self.day)),
+            "title" => Some(self.title.clone()),
+            "part_one_type" => Some(self.part_one_type.clone()),
+            "part_two_type" => Some(self.part_two_type.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// `.aoc/templates`, anchored at the crate root.
+pub fn templates_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(".aoc")
+        .join("templates")
+}
+
+/// Lists the templates in `.aoc/templates`, e.g. `grid` for `grid.rs`.
+pub fn template_names() -> io::Result<Vec<String>> {
+    let entries = match fs::read_dir(templates_dir()) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_suffix(".rs").map(String::from)
+        })
+        .collect();
+
+    names.sort();
+    Ok(names)
+}
+
+/// Loads the template `name` from `.aoc/templates/<name>.rs`. Without a name,
+/// `.aoc/templates/default.rs` is used if it exists, else [`DEFAULT_TEMPLATE`].
+pub fn load_template(name: Option<&str>) -> io::Result<String> {
+    let path = templates_dir().join(format!("{}.rs", name.unwrap_or("default")));
+
+    match (fs::read_to_string(&path), name) {
+        (Ok(template), _) => Ok(template),
+        (Err(e), None) if e.kind() == io::ErrorKind::NotFound => Ok(DEFAULT_TEMPLATE.into()),
+        (Err(e), Some(name)) if e.kind() == io::ErrorKind::NotFound => Err(io::Error::new(
+            e.kind(),
+            format!(
+                "no template \"{}\" in \"{}\", available: {}",
+                name,
+                templates_dir().display(),
+                template_names()?.join(", ")
+            ),
+        )),
+        (Err(e), _) => Err(e),
+    }
+}
+
+/// Replaces every `{{placeholder}}` in `template`. Unknown placeholders are an error so
+/// typos do not end up in generated code.
+pub fn render(template: &str, placeholders: &Placeholders) -> Result<String, String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find("}}")
+            .ok_or_else(|| "unclosed \"{{\" in template".to_string())?;
+        let name = rest[start + 2..start + end].trim();
+
+        match placeholders.get(name) {
+            Some(value) => output.push_str(&value),
+            None => return Err(format!("unknown placeholder \"{{{{{}}}}}\"", name)),
+        }
+
+        rest = &rest[start + end + 2..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// A file scaffold wants to write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileOp {
+    /// What the file is, e.g. `module` or `input`.
+    pub label: &'static str,
+    pub path: PathBuf,
+    pub contents: String,
+    /// Generated files may be overwritten with `--force`. Inputs, examples and their expected
+    /// answers are data and are never overwritten.
+    pub generated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Create,
+    /// The file exists and is kept.
+    Skip,
+    /// The file exists and is replaced after copying it to `backup`.
+    Overwrite {
+        backup: PathBuf,
+    },
+}
+
+impl FileOp {
+    /// Decides what to do with the file: existing files are skipped unless `force` is set
+    /// and the file is generated.
+    pub fn plan(&self, force: bool) -> Action {
+        match (self.path.exists(), force && self.generated) {
+            (false, _) => Action::Create,
+            (true, false) => Action::Skip,
+            (true, true) => Action::Overwrite {
+                backup: backup_path(&self.path),
+            },
+        }
+    }
+
+    pub fn apply(&self, action: &Action) -> io::Result<()> {
+        match action {
+            Action::Create => {
+                if let Some(parent) = self.path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&self.path, &self.contents)
+            }
+            Action::Skip => Ok(()),
+            Action::Overwrite { backup } => {
+                fs::copy(&self.path, backup)?;
+                fs::write(&self.path, &self.contents)
+            }
+        }
+    }
+
+    /// Describes `action`, e.g. `Created module "src/bin/07.rs"` or, for a dry run,
+    /// `Would create module "src/bin/07.rs"`.
+    pub fn describe(&self, action: &Action, dry_run: bool) -> String {
+        let verb = match (action, dry_run) {
+            (Action::Create, false) => "Created",
+            (Action::Create, true) => "Would create",
+            (Action::Skip, false) => "Skipped",
+            (Action::Skip, true) => "Would skip",
+            (Action::Overwrite { .. }, false) => "Overwrote",
+            (Action::Overwrite { .. }, true) => "Would overwrite",
+        };
+        let description = format!("{} {} \"{}\"", verb, self.label, self.path.display());
+
+        match action {
+            Action::Create => description,
+            Action::Skip => format!("{} (exists)", description),
+            Action::Overwrite { backup } => {
+                format!("{} (backup: \"{}\")", description, backup.display())
+            }
+        }
+    }
+}
+
+/// `<path>.bak`, or `<path>.bak.N` for the first `N` not taken yet.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let candidate = |suffix: String| path.with_file_name(format!("{}.bak{}", name, suffix));
+
+    let mut backup = candidate(String::new());
+    let mut n = 1;
+    while backup.exists() {
+        backup = candidate(format!(".{}", n));
+        n += 1;
+    }
+    backup
+}
+
+const TEST_MODULE_START: &str = "#[cfg(test)]\nmod tests";
+
+/// Replaces the test module of `module` with the one of `rendered`, keeping the solution
+/// code. The test module is expected at the end of the file, as in the templates.
+pub fn replace_test_module(module: &str, rendered: &str) -> Result<String, String> {
+    let tests = rendered
+        .find(TEST_MODULE_START)
+        .map(|start| &rendered[start..])
+        .ok_or_else(|| "template has no test module".to_string())?;
+
+    let code = match module.find(TEST_MODULE_START) {
+        Some(start) => &module[..start],
+        None => module,
+    };
+
+    let code = code.trim_end();
+    Ok(match code.is_empty() {
+        true => tests.to_string(),
+        false => format!("{}\n\n{}", code, tests),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut placeholders = Placeholders::new(2022, 7);
+        placeholders.part_two_type = "String".into();
+
+        assert_eq!(
+            render(
+                "// {{ title }} ({{year}}/{{day_padded}})\nfn f() -> {{part_two_type}}",
+                &placeholders
+            ),
+            Ok("// Day 7 (2022/07)\nfn f() -> String".into())
+        );
+        assert_eq!(
+            render("{{days}}", &placeholders),
+            Err("unknown placeholder \"{{days}}\"".into())
+        );
+        assert!(render("{{day", &placeholders).is_err());
+    }
+
+    #[test]
+    fn test_default_template() {
+        let module = render(DEFAULT_TEMPLATE, &Placeholders::new(2023, 1)).unwrap();
+        assert!(module.contains("pub fn part_one(input: &str) -> Option<u32>"));
+        assert!(module.contains("check(2023, 1, part_one, part_two)"));
+
+        // the template is a day module and nothing else.
+        let items: Vec<&str> = module
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with([' ', '}']))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                "pub fn part_one(input: &str) -> Option<u32> {",
+                "pub fn part_two(input: &str) -> Option<u32> {",
+                "fn main() {",
+                "#[cfg(test)]",
+                "mod tests {",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_test_module() {
+        let rendered = render(DEFAULT_TEMPLATE, &Placeholders::new(2022, 3)).unwrap();
+        let module = "pub fn part_one(input: &str) -> Option<u32> {\n    Some(1)\n}\n\n#[cfg(test)]\nmod tests {\n    // old\n}\n";
+
+        let replaced = replace_test_module(module, &rendered).unwrap();
+        assert!(replaced.starts_with(
+            "pub fn part_one(input: &str) -> Option<u32> {\n    Some(1)\n}\n\n#[cfg(test)]"
+        ));
+        assert!(!replaced.contains("// old"));
+        assert!(replaced.ends_with(&rendered[rendered.find(TEST_MODULE_START).unwrap()..]));
+
+        assert!(replace_test_module("fn main() {}\n", &rendered)
+            .unwrap()
+            .starts_with("fn main() {}\n\n#[cfg(test)]"));
+        assert!(replace_test_module(module, "fn main() {}").is_err());
+    }
+
+    #[test]
+    fn test_plan() {
+        let op = |path: PathBuf, generated| FileOp {
+            label: "module",
+            path,
+            contents: String::new(),
+            generated,
+        };
+        let existing = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let missing = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("missing.rs");
+
+        assert_eq!(op(missing, true).plan(true), Action::Create);
+        assert_eq!(op(existing.clone(), true).plan(false), Action::Skip);
+        assert_eq!(op(existing.clone(), false).plan(true), Action::Skip);
+        assert_eq!(
+            op(existing.clone(), true).plan(true),
+            Action::Overwrite {
+                backup: existing.with_file_name("Cargo.toml.bak")
+            }
+        );
+    }
+
+    #[test]
+    fn test_load_template() {
+        assert!(template_names().unwrap().contains(&"grid".to_string()));
+        assert!(load_template(Some("grid")).is_ok());
+
+        let e = load_template(Some("missing")).unwrap_err();
+        assert!(e.to_string().contains("available: "));
+    }
+}
@@ -0,0 +1,461 @@
+use crate::template::input::write_atomic;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the server said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    Correct,
+    Wrong {
+        hint: Option<Hint>,
+        /// How long to wait before the next attempt.
+        wait: Option<Duration>,
+    },
+    /// An answer was submitted too recently, nothing was checked.
+    TooRecent {
+        wait: Option<Duration>,
+    },
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    /// A reply this parser does not know, with the text of the page.
+    Unknown(String),
+}
+
+impl Reply {
+    /// Parses the HTML returned by `POST /YEAR/day/N/answer`.
+    pub fn parse(html: &str) -> Self {
+        let text = article_text(html);
+
+        if text.contains("That's the right answer") {
+            Reply::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = match (text.contains("too high"), text.contains("too low")) {
+                (true, false) => Some(Hint::TooHigh),
+                (false, true) => Some(Hint::TooLow),
+                _ => None,
+            };
+            Reply::Wrong {
+                hint,
+                wait: parse_wait(&text),
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Reply::TooRecent {
+                wait: parse_wait(&text),
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Reply::WrongLevel
+        } else {
+            Reply::Unknown(text)
+        }
+    }
+
+    pub fn wait(&self) -> Option<Duration> {
+        match self {
+            Reply::Wrong { wait, .. } | Reply::TooRecent { wait } => *wait,
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Reply::Correct => "correct",
+            Reply::Wrong {
+                hint: Some(Hint::TooHigh),
+                ..
+            } => "too_high",
+            Reply::Wrong {
+                hint: Some(Hint::TooLow),
+                ..
+            } => "too_low",
+            Reply::Wrong { hint: None, .. } => "wrong",
+            Reply::TooRecent { .. } => "too_recent",
+            Reply::WrongLevel => "wrong_level",
+            Reply::Unknown(_) => "unknown",
+        }
+    }
+
+    fn from_str(value: &str, wait: Option<Duration>) -> Option<Self> {
+        match value {
+            "correct" => Some(Reply::Correct),
+            "too_high" => Some(Reply::Wrong {
+                hint: Some(Hint::TooHigh),
+                wait,
+            }),
+            "too_low" => Some(Reply::Wrong {
+                hint: Some(Hint::TooLow),
+                wait,
+            }),
+            "wrong" => Some(Reply::Wrong { hint: None, wait }),
+            "too_recent" => Some(Reply::TooRecent { wait }),
+            "wrong_level" => Some(Reply::WrongLevel),
+            "unknown" => Some(Reply::Unknown(String::new())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reply::Correct => write!(f, "That's the right answer!"),
+            Reply::Wrong { hint, .. } => {
+                write!(f, "That's not the right answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, ", it is too high."),
+                    Some(Hint::TooLow) => write!(f, ", it is too low."),
+                    None => write!(f, "."),
+                }
+            }
+            Reply::TooRecent { .. } => write!(f, "An answer was submitted too recently."),
+            Reply::WrongLevel => write!(
+                f,
+                "This part is not the one to solve, is it solved already or still locked?"
+            ),
+            Reply::Unknown(text) => write!(f, "Unknown reply: {}", text),
+        }
+    }
+}
+
+/// Text of the `<article>` of a reply with tags removed and whitespace collapsed.
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map(|(_, rest)| rest.split("</article>").next().unwrap_or(rest))
+        .unwrap_or(html);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Finds how long to wait, e.g. in `You have 1m 5s left to wait` or
+/// `please wait 5 minutes before trying again`.
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((_, rest)) = text.split_once("You have ") {
+        let left = rest.split(" left to wait").next()?;
+        let seconds = left
+            .split_whitespace()
+            .map(|part| match part.split_at(part.len().saturating_sub(1)) {
+                (value, "h") => value.parse::<u64>().ok().map(|value| value * 3600),
+                (value, "m") => value.parse::<u64>().ok().map(|value| value * 60),
+                (value, "s") => value.parse::<u64>().ok(),
+                _ => None,
+            })
+            .sum::<Option<u64>>()?;
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let (_, rest) = text.split_once("wait ")?;
+    let mut words = rest.split_whitespace();
+    let count = match words.next()? {
+        "one" | "a" => 1,
+        count => count.parse::<u64>().ok()?,
+    };
+    match words.next()?.trim_end_matches(['.', ',']) {
+        "minute" | "minutes" => Some(Duration::from_secs(count * 60)),
+        "second" | "seconds" => Some(Duration::from_secs(count)),
+        _ => None,
+    }
+}
+
+/// A submitted answer. `time` is in seconds since the Unix epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub time: u64,
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub reply: Reply,
+}
+
+/// Every submitted answer, stored in `.aoc/history` as one line per attempt:
+/// `<time> <year> <day> <part> <reply> <wait_s or -> <answer>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    pub attempts: Vec<Attempt>,
+}
+
+/// `.aoc/history`, anchored at the crate root.
+pub fn history_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(".aoc")
+        .join("history")
+}
+
+fn parse_attempt(line: &str) -> Option<Attempt> {
+    let fields: Vec<&str> = line.splitn(7, ' ').collect();
+    let [time, year, day, part, reply, wait, answer] = fields[..] else {
+        return None;
+    };
+
+    let wait = match wait {
+        "-" => None,
+        wait => Some(Duration::from_secs(wait.parse().ok()?)),
+    };
+
+    Some(Attempt {
+        time: time.parse().ok()?,
+        year: year.parse().ok()?,
+        day: day.parse().ok()?,
+        part: part.parse().ok()?,
+        answer: answer.to_string(),
+        reply: Reply::from_str(reply, wait)?,
+    })
+}
+
+impl History {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut attempts = vec![];
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match parse_attempt(line) {
+                Some(attempt) => attempts.push(attempt),
+                None => {
+                    return Err(format!(
+                        "line {}: expected \"<time> <year> <day> <part> <reply> <wait> <answer>\"",
+                        i + 1
+                    ))
+                }
+            }
+        }
+
+        Ok(History { attempts })
+    }
+
+    /// Loads `.aoc/history`. A missing file means nothing was submitted yet.
+    pub fn load() -> io::Result<Self> {
+        let path = history_path();
+        match fs::read_to_string(&path) {
+            Ok(contents) => History::parse(&contents).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        write_atomic(&history_path(), &self.to_string())
+    }
+
+    /// Checks whether `answer` is worth submitting at `now`. Returns why not, if the part is
+    /// solved already, the answer is known to be wrong or the server asked to wait.
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+        now: u64,
+    ) -> Result<(), String> {
+        if let Some(last) = self.attempts.last() {
+            if let Some(wait) = last.reply.wait() {
+                let until = last.time + wait.as_secs();
+                if until > now {
+                    return Err(format!("wait {}s before submitting again", until - now));
+                }
+            }
+        }
+
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|attempt| (attempt.year, attempt.day, attempt.part) == (year, day, part));
+
+        let number = answer.parse::<i128>().ok();
+
+        for attempt in attempts {
+            let tried = attempt.answer.parse::<i128>().ok();
+
+            match (&attempt.reply, number.zip(tried)) {
+                (Reply::Correct, _) => {
+                    return Err(format!("already solved with {}", attempt.answer))
+                }
+                (Reply::Wrong { .. }, _) if attempt.answer == answer => {
+                    return Err(format!(
+                        "{} was already submitted: {}",
+                        answer, attempt.reply
+                    ))
+                }
+                (
+                    Reply::Wrong {
+                        hint: Some(Hint::TooHigh),
+                        ..
+                    },
+                    Some((number, tried)),
+                ) if number >= tried => {
+                    return Err(format!("{} is too high, {} already was", answer, tried))
+                }
+                (
+                    Reply::Wrong {
+                        hint: Some(Hint::TooLow),
+                        ..
+                    },
+                    Some((number, tried)),
+                ) if number <= tried => {
+                    return Err(format!("{} is too low, {} already was", answer, tried))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for History {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# time year day part reply wait_s answer")?;
+        for attempt in &self.attempts {
+            let wait = attempt
+                .reply
+                .wait()
+                .map_or("-".to_string(), |wait| wait.as_secs().to_string());
+            writeln!(
+                f,
+                "{} {} {} {} {} {} {}",
+                attempt.time,
+                attempt.year,
+                attempt.day,
+                attempt.part,
+                attempt.reply.as_str(),
+                wait,
+                attempt.answer
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(text: &str) -> Reply {
+        Reply::parse(&format!(
+            "<html><main><article><p>{}</p></article></main></html>",
+            text
+        ))
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        assert_eq!(
+            reply("That's the right answer!  You are <em>one gold star</em> closer."),
+            Reply::Correct
+        );
+        assert_eq!(
+            reply("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>"),
+            Reply::Wrong {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            reply("That's not the right answer.  Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again."),
+            Reply::Wrong {
+                hint: None,
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+        assert_eq!(
+            reply("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."),
+            Reply::TooRecent {
+                wait: Some(Duration::from_secs(65))
+            }
+        );
+        assert_eq!(
+            reply("You don't seem to be solving the right level.  Did you already complete it?"),
+            Reply::WrongLevel
+        );
+        assert_eq!(
+            reply("Something <em>new</em>."),
+            Reply::Unknown("Something new.".into())
+        );
+    }
+
+    fn attempt(time: u64, answer: &str, reply: Reply) -> Attempt {
+        Attempt {
+            time,
+            year: 2022,
+            day: 1,
+            part: 1,
+            answer: answer.into(),
+            reply,
+        }
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let history = History {
+            attempts: vec![
+                attempt(
+                    100,
+                    "24 000",
+                    Reply::Wrong {
+                        hint: None,
+                        wait: Some(Duration::from_secs(60)),
+                    },
+                ),
+                attempt(200, "24000", Reply::Correct),
+            ],
+        };
+
+        assert_eq!(History::parse(&history.to_string()), Ok(history));
+        assert!(History::parse("1 2022 1 1 maybe - 3").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let wrong = |hint| Reply::Wrong {
+            hint,
+            wait: Some(Duration::from_secs(60)),
+        };
+        let mut history = History {
+            attempts: vec![
+                attempt(100, "50", wrong(Some(Hint::TooHigh))),
+                attempt(200, "10", wrong(Some(Hint::TooLow))),
+                attempt(300, "abc", wrong(None)),
+            ],
+        };
+
+        assert!(history.check(2022, 1, 1, "30", 320).is_err());
+        assert_eq!(history.check(2022, 1, 1, "30", 360), Ok(()));
+        assert!(history.check(2022, 1, 1, "50", 400).is_err());
+        assert!(history.check(2022, 1, 1, "60", 400).is_err());
+        assert!(history.check(2022, 1, 1, "10", 400).is_err());
+        assert!(history.check(2022, 1, 1, "abc", 400).is_err());
+        assert_eq!(history.check(2022, 1, 2, "60", 400), Ok(()));
+
+        history.attempts.push(attempt(400, "30", Reply::Correct));
+        assert!(history.check(2022, 1, 1, "31", 500).is_err());
+    }
+}